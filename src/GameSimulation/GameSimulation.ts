@@ -1,44 +1,414 @@
 import { Entity, Body } from './components.js';
-import { World } from './world.js';
+import {
+  World,
+  Bounds,
+  Broadphase,
+  CollisionModel,
+  Edge,
+  EdgeBehavior,
+  DebugContext,
+  RaycastHit,
+  UpdatePhase,
+  WorldConfig,
+} from './world.js';
+import { CellCoordinates, GridMode, SpatialGrid } from './spatial-grid.js';
+import { samplePoissonDisk } from './poisson-disk.js';
+import { RngKind } from './rng.js';
+import { parseScene } from './scene.js';
+import { EntityRecord, takeSnapshot } from './snapshot.js';
+import { CollisionEvent } from './events.js';
+
+export type {
+  Bounds,
+  Broadphase,
+  CollisionModel,
+  DebugContext,
+  Edge,
+  EdgeBehavior,
+  FlowField,
+  RaycastHit,
+  UpdatePhase,
+  WorldConfig,
+} from './world.js';
+export type { CellCoordinates, GridMode } from './spatial-grid.js';
+export type { RngKind } from './rng.js';
+export type { SceneBody, SceneDescription } from './scene.js';
+export type { EntityRecord, SnapshotDelta } from './snapshot.js';
+export type { CollisionEvent } from './events.js';
+export { applyDelta, diffSnapshots } from './snapshot.js';
+
+export interface GameSimulationOptions {
+  seed?: number;
+  rng?: RngKind;
+}
 
 export interface GameSimulationState {
   entities: readonly Entity[];
   width: number;
   height: number;
   gridCellSize: number;
+  speeds?: Float32Array;
+  normalizedSpeeds?: Float32Array;
+  ids?: Uint32Array;
+}
+
+export interface EntityInspection {
+  id: number;
+  x: number;
+  y: number;
+  vx: number;
+  vy: number;
+  radius: number;
+  pinned: boolean;
+  hp?: { current: number; max: number };
+}
+
+export interface SpawnBodyOptions {
+  drag?: number;
+  gravityScale?: number;
+  zeroNetMomentum?: boolean;
+  placementStream?: number;
 }
 
 export type SimulationCommand =
-  | { type: 'spawn_random_entities'; count: number; radius: number; speed: number }
+  | ({
+      type: 'spawn_random_entities';
+      count: number;
+      radius: number;
+      speed: number;
+    } & SpawnBodyOptions)
+  | ({
+      type: 'spawn_random_jittered';
+      count: number;
+      radius: number;
+      speed: number;
+      jitter: number;
+    } & SpawnBodyOptions)
+  | ({ type: 'spawn_poisson'; count: number; radius: number; speed: number } & SpawnBodyOptions)
+  | ({ type: 'spawn_random_static'; count: number; radius: number } & SpawnBodyOptions)
+  | ({
+      type: 'spawn_random_nonoverlap_fast';
+      count: number;
+      radius: number;
+      speed: number;
+    } & SpawnBodyOptions)
+  | { type: 'demo_explosion'; count: number; radius: number }
   | { type: 'remove_half_entities' }
+  | { type: 'remove_fraction'; fraction: number }
+  | { type: 'reset_scene' }
+  | { type: 'set_next_entity_id'; id: number }
+  | { type: 'remove_random_fraction'; fraction: number }
+  | { type: 'compact' }
   | { type: 'adjust_grid_cell_size'; delta: number }
   | { type: 'set_grid_cell_size'; size: number }
-  | { type: 'scale_radius'; factor: number };
+  | { type: 'set_grid_mode'; mode: GridMode }
+  | { type: 'set_two_level_grid'; enabled: boolean }
+  | { type: 'set_broadphase'; mode: Broadphase; threshold?: number }
+  | { type: 'scale_radius'; factor: number }
+  | { type: 'scale_entity_radius'; id: number; factor: number }
+  | { type: 'randomize_velocities'; maxSpeed: number }
+  | { type: 'scale_velocities'; factor: number }
+  | { type: 'write_velocities'; velocities: ArrayLike<number> }
+  | {
+      type: 'add_emitter';
+      x: number;
+      y: number;
+      rate: number;
+      speed: number;
+      spread: number;
+      angle: number;
+      radius: number;
+    }
+  | { type: 'clear_emitters' }
+  | { type: 'set_collision_model'; model: CollisionModel }
+  | { type: 'set_position_correction'; slop: number; percent: number }
+  | { type: 'set_correction_damping'; damping: number }
+  | { type: 'set_max_neighbors_per_body'; limit: number }
+  | { type: 'set_contact_passes'; passes: number }
+  | { type: 'set_rotational_collisions'; enabled: boolean; friction?: number }
+  | { type: 'set_wall_restitution'; restitution: number }
+  | { type: 'set_body_restitution'; restitution: number }
+  | { type: 'set_edge_behavior'; edge: Edge; behavior: EdgeBehavior }
+  | { type: 'set_wrap_x'; enabled: boolean }
+  | { type: 'set_wrap_y'; enabled: boolean }
+  | { type: 'set_speed_export'; enabled: boolean }
+  | { type: 'set_normalized_speed_export'; enabled: boolean }
+  | { type: 'set_id_export'; enabled: boolean }
+  | { type: 'set_entity_budget'; budget: number }
+  | { type: 'remove_spring'; handle: number }
+  | { type: 'pin_entity'; id: number; pinned: boolean }
+  | { type: 'set_entity_body'; id: number; x: number; y: number; vx: number; vy: number }
+  | { type: 'set_entity_aspect'; id: number; aspect: number }
+  | { type: 'set_anti_stick'; enabled: boolean }
+  | { type: 'set_merge_on_collision'; enabled: boolean }
+  | { type: 'set_fragmentation'; sizeThreshold: number; speedThreshold: number }
+  | { type: 'set_heat_decay'; decay: number }
+  | { type: 'set_event_capacity'; capacity: number }
+  | { type: 'set_fast_spawn'; enabled: boolean }
+  | { type: 'set_placement_ignores_existing'; enabled: boolean }
+  | { type: 'apply_force'; id: number; fx: number; fy: number }
+  | { type: 'apply_forces'; forces: ArrayLike<number> }
+  | { type: 'set_damping'; damping: number }
+  | { type: 'set_entity_drag'; id: number; drag: number | null }
+  | { type: 'set_debug_checks'; enabled: boolean }
+  | { type: 'set_gravity'; x: number; y: number }
+  | { type: 'set_brownian'; strength: number }
+  | { type: 'set_pointer_gravity'; x: number; y: number; strength: number }
+  | {
+      type: 'set_flow_field';
+      cols: number;
+      rows: number;
+      vectors: ArrayLike<number>;
+      strength: number;
+    }
+  | { type: 'clear_flow_field' }
+  | { type: 'add_frozen_region'; minX: number; minY: number; maxX: number; maxY: number }
+  | { type: 'clear_frozen_regions' }
+  | { type: 'set_entity_gravity_scale'; id: number; scale: number }
+  | { type: 'set_entity_one_way'; id: number; nx: number; ny: number }
+  | { type: 'set_bounds_inset'; inset: number };
 
 export class GameSimulation {
   private world: World;
+  private exportSpeeds = false;
+  private exportNormalizedSpeeds = false;
+  private exportIds = false;
+  private peakEntityCount = 0;
 
-  constructor() {
-    this.world = new World();
+  constructor(options: GameSimulationOptions = {}) {
+    this.world = new World(options.seed, options.rng);
   }
 
-  next_tick(commands: SimulationCommand[], deltaTime: number): void {
+  static from_scene(json: string, options: GameSimulationOptions = {}): GameSimulation {
+    const scene = parseScene(json);
+    const simulation = new GameSimulation(options);
+    const world = simulation.world;
+
+    world.resize(scene.width, scene.height);
+    if (scene.cellSize !== undefined) {
+      world.setGridCellSize(scene.cellSize);
+    }
+    for (const body of scene.bodies) {
+      const entity = world.createEntity(new Body(body.x, body.y, body.vx, body.vy, body.radius));
+      world.addEntityAt(entity);
+    }
+    simulation.trackPeak();
+
+    return simulation;
+  }
+
+  next_tick(
+    commands: SimulationCommand[],
+    deltaTime: number,
+    observer?: (phase: UpdatePhase) => void
+  ): boolean {
     if (commands.length > 0) {
       this.applyCommands(commands);
+      this.trackPeak();
     }
 
-    if (deltaTime > 0) {
+    if (!(deltaTime > 0) || !Number.isFinite(deltaTime)) {
+      return false;
+    }
+
+    this.world.update(deltaTime, observer);
+    this.trackPeak();
+    return true;
+  }
+
+  settle(maxFrames: number, velocityThreshold: number, deltaTime = 1 / 60): number {
+    if (!(deltaTime > 0) || !Number.isFinite(deltaTime)) return 0;
+
+    const limit = Math.max(Math.floor(maxFrames), 0);
+    let frames = 0;
+    while (frames < limit && this.world.getMaxSpeed() >= velocityThreshold) {
       this.world.update(deltaTime);
+      frames++;
     }
+    this.trackPeak();
+    return frames;
+  }
+
+  add_spring(idA: number, idB: number, restLength: number, stiffness: number, damping: number): number {
+    return this.world.addSpring(idA, idB, restLength, stiffness, damping);
+  }
+
+  try_spawn_at(x: number, y: number, radius: number, vx: number, vy: number): number {
+    const id = this.world.trySpawnAt(x, y, radius, vx, vy);
+    this.trackPeak();
+    return id;
+  }
+
+  remove_entities(ids: ArrayLike<number>): number {
+    return this.world.removeEntitiesById(ids);
   }
 
   get_state(): GameSimulationState {
-    return {
-      entities: this.world.entities,
-      width: this.world.width,
-      height: this.world.height,
-      gridCellSize: this.world.getGridCellSize(),
+    return this.buildState(this.world);
+  }
+
+  inspect_entity(id: number): EntityInspection | null {
+    const entity = this.world.getEntity(id);
+    const body = entity?.body;
+    if (!entity || !body) return null;
+
+    const inspection: EntityInspection = {
+      id: entity.id,
+      x: body.x,
+      y: body.y,
+      vx: body.vx,
+      vy: body.vy,
+      radius: body.radius,
+      pinned: body.pinned,
     };
+    if (entity.hp) {
+      inspection.hp = { current: entity.hp.current, max: entity.hp.max };
+    }
+    return inspection;
+  }
+
+  get_snapshot(): EntityRecord[] {
+    return takeSnapshot(this.world.entities);
+  }
+
+  get_spawn_bounds(): Bounds {
+    return this.world.getSpawnBounds();
+  }
+
+  get_config(): WorldConfig {
+    return this.world.getConfig();
+  }
+
+  get_neighbor_count(id: number): number {
+    return this.world.getNeighborCount(id);
+  }
+
+  are_colliding(idA: number, idB: number): boolean {
+    return this.world.areColliding(idA, idB);
+  }
+
+  count_in_radius(x: number, y: number, radius: number): number {
+    return this.world.countInRadius(x, y, radius);
+  }
+
+  count_overlaps(): number {
+    return this.world.countOverlaps();
+  }
+
+  read_velocities(): Float32Array {
+    return this.world.readVelocities();
+  }
+
+  get_momentum(): { x: number; y: number } {
+    return this.world.getMomentum();
+  }
+
+  get_velocity_arrows(scale: number): Float32Array {
+    return this.world.getVelocityArrows(scale);
+  }
+
+  get_center_of_mass(): { x: number; y: number } | null {
+    return this.world.getCenterOfMass();
+  }
+
+  get_densest_cell(): { x: number; y: number; count: number } | null {
+    return this.world.getDensestCell();
+  }
+
+  query_k_nearest(x: number, y: number, k: number): number[] {
+    return this.world.nearestIds(x, y, k);
+  }
+
+  pick(x: number, y: number): number | null {
+    return this.world.pick(x, y);
+  }
+
+  raycast(x: number, y: number, dx: number, dy: number, maxDistance: number): RaycastHit | null {
+    return this.world.raycast(x, y, dx, dy, maxDistance);
+  }
+
+  query_capsule(x0: number, y0: number, x1: number, y1: number, radius: number): number[] {
+    return this.world.queryCapsule(x0, y0, x1, y1, radius);
+  }
+
+  point_to_cell(x: number, y: number): CellCoordinates {
+    return this.world.spatialGrid.pointToCell(x, y);
+  }
+
+  get_cell_contents(col: number, row: number): number[] {
+    return this.world.getCellContents(col, row);
+  }
+
+  get_heat_field(): Float32Array {
+    return this.world.heatField;
+  }
+
+  get_debug_context(entityIds: number[] = []): DebugContext {
+    return this.world.debugContext(entityIds);
+  }
+
+  get_frame(): number {
+    return this.world.frame;
+  }
+
+  get_seed(): number {
+    return this.world.seed;
+  }
+
+  get_rng_kind(): RngKind {
+    return this.world.rng.kind;
+  }
+
+  get_evicted_count(): number {
+    return this.world.lastEvictedCount;
+  }
+
+  get_moved_ids(): readonly number[] {
+    return this.world.movedIds;
+  }
+
+  get_max_displacement(): number {
+    return this.world.lastMaxDisplacement;
+  }
+
+  get_contact_pairs(): readonly number[] {
+    return this.world.contactPairs;
+  }
+
+  drain_collision_events(): CollisionEvent[] {
+    return this.world.collisionEvents.drain();
+  }
+
+  get_dropped_event_count(): number {
+    return this.world.collisionEvents.dropped;
+  }
+
+  get_peak_entity_count(): number {
+    return this.peakEntityCount;
+  }
+
+  reset_peak(): void {
+    this.peakEntityCount = this.world.entities.length;
+  }
+
+  get_merge_count(): number {
+    return this.world.lastMergeCount;
+  }
+
+  get_fragment_count(): number {
+    return this.world.lastFragmentCount;
+  }
+
+  get_pair_check_count(): number {
+    return this.world.lastPairCheckCount;
+  }
+
+  get_anti_stick_count(): number {
+    return this.world.antiStickFiredCount;
+  }
+
+  get_memory_usage(): number {
+    return this.world.estimateMemoryUsage();
   }
 
   preview_state(deltaTime: number): GameSimulationState {
@@ -47,25 +417,136 @@ export class GameSimulation {
       previewWorld.update(deltaTime);
     }
 
-    return {
-      entities: previewWorld.entities,
-      width: previewWorld.width,
-      height: previewWorld.height,
-      gridCellSize: previewWorld.getGridCellSize(),
+    return this.buildState(previewWorld);
+  }
+
+  preview_entity(id: number, deltaTime: number, steps: number): Float32Array | null {
+    if (!this.world.getEntity(id)?.body) return null;
+
+    const previewWorld = this.world.clone();
+    const stepCount = deltaTime > 0 ? Math.max(Math.floor(steps), 0) : 0;
+    const positions = new Float32Array(stepCount * 2);
+    for (let i = 0; i < stepCount; i++) {
+      previewWorld.update(deltaTime);
+      const body = previewWorld.getEntity(id)?.body;
+      if (!body) {
+        return positions.subarray(0, i * 2);
+      }
+
+      positions[i * 2] = body.x;
+      positions[i * 2 + 1] = body.y;
+    }
+
+    return positions;
+  }
+
+  private trackPeak(): void {
+    this.peakEntityCount = Math.max(this.peakEntityCount, this.world.entities.length);
+  }
+
+  private buildState(world: World): GameSimulationState {
+    const state: GameSimulationState = {
+      entities: world.entities,
+      width: world.width,
+      height: world.height,
+      gridCellSize: world.getGridCellSize(),
     };
+
+    if (this.exportSpeeds || this.exportNormalizedSpeeds) {
+      const entities = world.entities;
+      const speeds = new Float32Array(entities.length);
+      let maxSpeed = 0;
+      for (let i = 0; i < entities.length; i++) {
+        const body = entities[i].body;
+        if (body) {
+          speeds[i] = Math.sqrt(body.vx * body.vx + body.vy * body.vy);
+          maxSpeed = Math.max(maxSpeed, speeds[i]);
+        }
+      }
+
+      if (this.exportSpeeds) {
+        state.speeds = speeds;
+      }
+      if (this.exportNormalizedSpeeds) {
+        const normalized = new Float32Array(entities.length);
+        if (maxSpeed > 0) {
+          for (let i = 0; i < entities.length; i++) {
+            normalized[i] = speeds[i] / maxSpeed;
+          }
+        }
+        state.normalizedSpeeds = normalized;
+      }
+    }
+
+    if (this.exportIds) {
+      const entities = world.entities;
+      const ids = new Uint32Array(entities.length);
+      for (let i = 0; i < entities.length; i++) {
+        ids[i] = entities[i].id;
+      }
+      state.ids = ids;
+    }
+
+    return state;
   }
 
   private applyCommands(commands: SimulationCommand[]): void {
     for (const command of commands) {
       switch (command.type) {
         case 'spawn_random_entities': {
-          this.spawnRandomEntities(command.count, command.radius, command.speed);
+          this.spawnRandomEntities(command.count, command.radius, command.speed, command);
+          break;
+        }
+        case 'spawn_random_jittered': {
+          this.spawnRandomEntities(
+            command.count,
+            command.radius,
+            command.speed,
+            command,
+            command.jitter
+          );
+          break;
+        }
+        case 'spawn_poisson': {
+          this.spawnPoissonEntities(command.count, command.radius, command.speed, command);
+          break;
+        }
+        case 'spawn_random_static': {
+          this.spawnStaticEntities(command.count, command.radius, command);
+          break;
+        }
+        case 'spawn_random_nonoverlap_fast': {
+          this.spawnNonOverlappingEntities(command.count, command.radius, command.speed, command);
+          break;
+        }
+        case 'demo_explosion': {
+          this.spawnExplosion(command.count, command.radius);
           break;
         }
         case 'remove_half_entities': {
           this.world.removeEntities();
           break;
         }
+        case 'remove_fraction': {
+          this.world.removeFraction(command.fraction);
+          break;
+        }
+        case 'reset_scene': {
+          this.world.resetScene();
+          break;
+        }
+        case 'set_next_entity_id': {
+          this.world.setNextEntityId(command.id);
+          break;
+        }
+        case 'remove_random_fraction': {
+          this.world.removeRandomFraction(command.fraction);
+          break;
+        }
+        case 'compact': {
+          this.world.compact();
+          break;
+        }
         case 'adjust_grid_cell_size': {
           this.world.adjustGridCellSize(command.delta);
           break;
@@ -74,10 +555,227 @@ export class GameSimulation {
           this.world.setGridCellSize(command.size);
           break;
         }
+        case 'set_grid_mode': {
+          this.world.setGridMode(command.mode);
+          break;
+        }
+        case 'set_two_level_grid': {
+          this.world.twoLevelGrid = command.enabled;
+          break;
+        }
+        case 'set_broadphase': {
+          this.world.broadphase = command.mode;
+          if (command.threshold !== undefined) {
+            this.world.bruteForceThreshold = Math.max(Math.floor(command.threshold), 0);
+          }
+          break;
+        }
         case 'scale_radius': {
           this.world.scaleRadii(command.factor);
           break;
         }
+        case 'scale_entity_radius': {
+          this.world.scaleEntityRadius(command.id, command.factor);
+          break;
+        }
+        case 'randomize_velocities': {
+          this.world.randomizeVelocities(command.maxSpeed);
+          break;
+        }
+        case 'scale_velocities': {
+          this.world.scaleVelocities(command.factor);
+          break;
+        }
+        case 'write_velocities': {
+          this.world.writeVelocities(command.velocities);
+          break;
+        }
+        case 'add_emitter': {
+          this.world.addEmitter(
+            command.x,
+            command.y,
+            command.rate,
+            command.speed,
+            command.spread,
+            command.angle,
+            command.radius
+          );
+          break;
+        }
+        case 'clear_emitters': {
+          this.world.clearEmitters();
+          break;
+        }
+        case 'set_collision_model': {
+          this.world.collisionModel = command.model;
+          break;
+        }
+        case 'set_position_correction': {
+          this.world.correctionSlop = Math.max(command.slop, 0);
+          this.world.correctionPercent = Math.min(Math.max(command.percent, 0), 1);
+          break;
+        }
+        case 'set_correction_damping': {
+          this.world.correctionDamping = Math.max(command.damping, 0);
+          break;
+        }
+        case 'set_max_neighbors_per_body': {
+          this.world.maxNeighborsPerBody = Math.max(Math.floor(command.limit), 0);
+          break;
+        }
+        case 'set_contact_passes': {
+          this.world.contactPasses = Math.min(Math.max(Math.floor(command.passes), 1), 32);
+          break;
+        }
+        case 'set_rotational_collisions': {
+          this.world.rotationalCollisions = command.enabled;
+          if (command.friction !== undefined) {
+            this.world.contactFriction = Math.max(command.friction, 0);
+          }
+          break;
+        }
+        case 'set_wall_restitution': {
+          this.world.wallRestitution = Math.min(Math.max(command.restitution, 0), 1);
+          break;
+        }
+        case 'set_body_restitution': {
+          this.world.bodyRestitution = Math.min(Math.max(command.restitution, 0), 1);
+          break;
+        }
+        case 'set_edge_behavior': {
+          this.world.edgeBehaviors[command.edge] = command.behavior;
+          break;
+        }
+        case 'set_wrap_x': {
+          this.world.setWrap('x', command.enabled);
+          break;
+        }
+        case 'set_wrap_y': {
+          this.world.setWrap('y', command.enabled);
+          break;
+        }
+        case 'set_speed_export': {
+          this.exportSpeeds = command.enabled;
+          break;
+        }
+        case 'set_normalized_speed_export': {
+          this.exportNormalizedSpeeds = command.enabled;
+          break;
+        }
+        case 'set_id_export': {
+          this.exportIds = command.enabled;
+          break;
+        }
+        case 'set_entity_budget': {
+          this.world.entityBudget = Math.max(Math.floor(command.budget), 0);
+          break;
+        }
+        case 'remove_spring': {
+          this.world.removeSpring(command.handle);
+          break;
+        }
+        case 'pin_entity': {
+          this.world.setEntityPinned(command.id, command.pinned);
+          break;
+        }
+        case 'set_entity_body': {
+          this.world.setEntityBody(command.id, command.x, command.y, command.vx, command.vy);
+          break;
+        }
+        case 'set_entity_aspect': {
+          this.world.setEntityAspect(command.id, command.aspect);
+          break;
+        }
+        case 'set_anti_stick': {
+          this.world.antiStick = command.enabled;
+          break;
+        }
+        case 'set_merge_on_collision': {
+          this.world.mergeOnCollision = command.enabled;
+          break;
+        }
+        case 'set_fragmentation': {
+          this.world.fragmentSizeThreshold = command.sizeThreshold;
+          this.world.fragmentSpeedThreshold = command.speedThreshold;
+          break;
+        }
+        case 'set_heat_decay': {
+          this.world.heatDecay = Math.min(Math.max(command.decay, 0), 1);
+          break;
+        }
+        case 'set_event_capacity': {
+          this.world.collisionEvents.setCapacity(command.capacity);
+          break;
+        }
+        case 'set_fast_spawn': {
+          this.world.fastSpawn = command.enabled;
+          break;
+        }
+        case 'set_placement_ignores_existing': {
+          this.world.placementIgnoresExisting = command.enabled;
+          break;
+        }
+        case 'apply_force': {
+          this.world.applyForce(command.id, command.fx, command.fy);
+          break;
+        }
+        case 'apply_forces': {
+          this.world.applyForces(command.forces);
+          break;
+        }
+        case 'set_damping': {
+          this.world.damping = Math.max(command.damping, 0);
+          break;
+        }
+        case 'set_entity_drag': {
+          this.world.setEntityDrag(command.id, command.drag);
+          break;
+        }
+        case 'set_debug_checks': {
+          this.world.debugChecks = command.enabled;
+          break;
+        }
+        case 'set_gravity': {
+          this.world.gravityX = command.x;
+          this.world.gravityY = command.y;
+          break;
+        }
+        case 'set_brownian': {
+          this.world.brownianStrength = Math.max(command.strength, 0);
+          break;
+        }
+        case 'set_pointer_gravity': {
+          this.world.setPointerGravity(command.x, command.y, command.strength);
+          break;
+        }
+        case 'set_flow_field': {
+          this.world.setFlowField(command.cols, command.rows, command.vectors, command.strength);
+          break;
+        }
+        case 'clear_flow_field': {
+          this.world.clearFlowField();
+          break;
+        }
+        case 'add_frozen_region': {
+          this.world.addFrozenRegion(command.minX, command.minY, command.maxX, command.maxY);
+          break;
+        }
+        case 'clear_frozen_regions': {
+          this.world.clearFrozenRegions();
+          break;
+        }
+        case 'set_entity_gravity_scale': {
+          this.world.setEntityGravityScale(command.id, command.scale);
+          break;
+        }
+        case 'set_entity_one_way': {
+          this.world.setEntityOneWay(command.id, command.nx, command.ny);
+          break;
+        }
+        case 'set_bounds_inset': {
+          this.world.setBoundsInset(command.inset);
+          break;
+        }
         default: {
           const exhaustiveCheck: never = command;
           throw new Error(`Unhandled SimulationCommand: ${JSON.stringify(exhaustiveCheck)}`);
@@ -86,19 +784,190 @@ export class GameSimulation {
     }
   }
 
-  private spawnRandomEntities(count: number, radius: number, speed: number): void {
-    for (let i = 0; i < count; i++) {
-      const angle = Math.random() * Math.PI * 2;
-      const entity = new Entity(
-        new Body(
-          0,
-          0,
-          Math.cos(angle) * speed,
-          Math.sin(angle) * speed,
-          radius
-        )
+  private spawnRandomEntities(
+    count: number,
+    radius: number,
+    speed: number,
+    options: SpawnBodyOptions,
+    jitter = 0
+  ): void {
+    const spawnCount = this.world.reserveSlots(count);
+    const bodies: Body[] = [];
+    this.world.beginPlacement(options.placementStream ?? 0);
+    for (let i = 0; i < spawnCount; i++) {
+      const angle = this.world.rng.nextFloat() * Math.PI * 2;
+      const bodySpeed =
+        jitter > 0 ? speed * (1 + (this.world.rng.nextFloat() - 0.5) * jitter) : speed;
+      const body = new Body(0, 0, Math.cos(angle) * bodySpeed, Math.sin(angle) * bodySpeed, radius);
+      this.applySpawnOptions(body, options);
+      this.world.addEntity(this.world.createEntity(body));
+      bodies.push(body);
+    }
+    this.finishSpawnBatch(bodies, options);
+  }
+
+  private spawnStaticEntities(count: number, radius: number, options: SpawnBodyOptions): void {
+    const spawnCount = this.world.reserveSlots(count);
+    this.world.beginPlacement(options.placementStream ?? 0);
+    for (let i = 0; i < spawnCount; i++) {
+      const body = new Body(0, 0, 0, 0, radius);
+      this.applySpawnOptions(body, options);
+      this.world.addEntity(this.world.createEntity(body));
+    }
+  }
+
+  private spawnExplosion(count: number, radius: number): void {
+    const world = this.world;
+    const available = Math.max(World.MAX_ENTITIES - world.entities.length, 0);
+    const spawnCount = world.reserveSlots(Math.min(count, available));
+    const bounds = world.getSpawnBounds();
+    const centerX = (bounds.minX + bounds.maxX) / 2;
+    const centerY = (bounds.minY + bounds.maxY) / 2;
+    const spacing = radius * 1.2;
+    const goldenAngle = Math.PI * (3 - Math.sqrt(5));
+    const startAngle = world.rng.nextFloat() * Math.PI * 2;
+    const blastSpeed = 240;
+
+    const bodies: Body[] = [];
+    for (let i = 0; i < spawnCount; i++) {
+      const angle = startAngle + i * goldenAngle;
+      const distance = spacing * Math.sqrt(i);
+      const body = new Body(
+        centerX + Math.cos(angle) * distance,
+        centerY + Math.sin(angle) * distance,
+        0,
+        0,
+        radius
       );
-      this.world.addEntity(entity);
+      bodies.push(body);
+      world.addEntityAt(world.createEntity(body));
+    }
+
+    for (const body of bodies) {
+      const dx = body.x - centerX;
+      const dy = body.y - centerY;
+      const distance = Math.sqrt(dx * dx + dy * dy);
+      const angle = distance > 0 ? Math.atan2(dy, dx) : world.rng.nextFloat() * Math.PI * 2;
+      const speed = blastSpeed * world.rng.nextRange(0.75, 1.25);
+      body.vx = Math.cos(angle) * speed;
+      body.vy = Math.sin(angle) * speed;
+    }
+  }
+
+  private spawnPoissonEntities(
+    count: number,
+    radius: number,
+    speed: number,
+    options: SpawnBodyOptions
+  ): void {
+    const bounds = this.world.getSpawnBounds();
+    const points = samplePoissonDisk(
+      bounds.minX + radius,
+      bounds.minY + radius,
+      bounds.maxX - radius,
+      bounds.maxY - radius,
+      radius * 2,
+      count,
+      () => this.world.rng.nextFloat()
+    );
+
+    const available = Math.max(World.MAX_ENTITIES - this.world.entities.length, 0);
+    const spawnCount = this.world.reserveSlots(Math.min(points.length, available));
+    const bodies: Body[] = [];
+    for (let i = 0; i < spawnCount; i++) {
+      const point = points[i];
+      const angle = this.world.rng.nextFloat() * Math.PI * 2;
+      const body = new Body(point.x, point.y, Math.cos(angle) * speed, Math.sin(angle) * speed, radius);
+      this.applySpawnOptions(body, options);
+      this.world.addEntityAt(this.world.createEntity(body));
+      bodies.push(body);
+    }
+    this.finishSpawnBatch(bodies, options);
+  }
+
+  private spawnNonOverlappingEntities(
+    count: number,
+    radius: number,
+    speed: number,
+    options: SpawnBodyOptions
+  ): void {
+    const world = this.world;
+    const spawnCount = world.entityBudget > 0 ? Math.min(count, world.entityBudget) : count;
+    world.lastEvictedCount = 0;
+    const grid = new SpatialGrid(world.width, world.height, Math.max(radius * 2, 8));
+
+    let maxRadius = radius;
+    for (const entity of world.entities) {
+      if (!entity.body) continue;
+
+      grid.insert(entity);
+      maxRadius = Math.max(maxRadius, entity.body.radius);
+    }
+
+    const bounds = world.getSpawnBounds();
+    const spanX = bounds.maxX - bounds.minX - radius * 2;
+    const spanY = bounds.maxY - bounds.minY - radius * 2;
+    if (spanX < 0 || spanY < 0) return;
+
+    const bodies: Body[] = [];
+    let evicted = 0;
+    for (let i = 0; i < spawnCount; i++) {
+      for (let attempt = 0; attempt < 30; attempt++) {
+        const x = bounds.minX + radius + world.rng.nextFloat() * spanX;
+        const y = bounds.minY + radius + world.rng.nextFloat() * spanY;
+
+        let collides = false;
+        for (const other of grid.getEntitiesInRadius(x, y, radius + maxRadius)) {
+          const body = other.body;
+          if (!body) continue;
+
+          const dx = x - body.x;
+          const dy = y - body.y;
+          const minDist = radius + body.radius;
+          if (dx * dx + dy * dy < minDist * minDist) {
+            collides = true;
+            break;
+          }
+        }
+        if (collides) continue;
+
+        world.reserveSlots(1);
+        evicted += world.lastEvictedCount;
+
+        const angle = world.rng.nextFloat() * Math.PI * 2;
+        const body = new Body(x, y, Math.cos(angle) * speed, Math.sin(angle) * speed, radius);
+        this.applySpawnOptions(body, options);
+        const entity = world.createEntity(body);
+        grid.insert(entity);
+        world.addEntityAt(entity);
+        bodies.push(body);
+        break;
+      }
+    }
+    world.lastEvictedCount = evicted;
+    this.finishSpawnBatch(bodies, options);
+  }
+
+  private applySpawnOptions(body: Body, options: SpawnBodyOptions): void {
+    body.drag = options.drag ?? null;
+    body.gravityScale = options.gravityScale ?? 1;
+  }
+
+  private finishSpawnBatch(bodies: readonly Body[], options: SpawnBodyOptions): void {
+    if (!options.zeroNetMomentum || bodies.length === 0) return;
+
+    let meanX = 0;
+    let meanY = 0;
+    for (const body of bodies) {
+      meanX += body.vx;
+      meanY += body.vy;
+    }
+    meanX /= bodies.length;
+    meanY /= bodies.length;
+
+    for (const body of bodies) {
+      body.vx -= meanX;
+      body.vy -= meanY;
     }
   }
 }
@@ -1,4 +1,14 @@
 export class Body {
+  public pinned = false;
+  public aspect = 1;
+  public ax = 0;
+  public ay = 0;
+  public drag: number | null = null;
+  public gravityScale = 1;
+  public oneWay: { x: number; y: number } | null = null;
+  public angle = 0;
+  public angularVelocity = 0;
+
   constructor(
     public x: number,
     public y: number,
@@ -6,6 +16,20 @@ export class Body {
     public vy: number,
     public radius: number
   ) {}
+
+  clone(): Body {
+    const cloned = new Body(this.x, this.y, this.vx, this.vy, this.radius);
+    cloned.pinned = this.pinned;
+    cloned.aspect = this.aspect;
+    cloned.ax = this.ax;
+    cloned.ay = this.ay;
+    cloned.drag = this.drag;
+    cloned.gravityScale = this.gravityScale;
+    cloned.oneWay = this.oneWay ? { ...this.oneWay } : null;
+    cloned.angle = this.angle;
+    cloned.angularVelocity = this.angularVelocity;
+    return cloned;
+  }
 }
 
 export class HP {
@@ -17,17 +41,10 @@ export class Payload {
 }
 
 export class Entity {
-  private static nextId = 1;
-  public readonly id: number;
-
   constructor(
+    public readonly id: number,
     public body?: Body,
     public hp?: HP,
-    public payload?: Payload,
-    id?: number
-  ) {
-    const assignedId = id ?? Entity.nextId++;
-    this.id = assignedId;
-    Entity.nextId = Math.max(Entity.nextId, assignedId + 1);
-  }
+    public payload?: Payload
+  ) {}
 }
@@ -0,0 +1,89 @@
+export interface CollisionEvent {
+  idA: number;
+  idB: number;
+  x: number;
+  y: number;
+  speed: number;
+}
+
+export class CollisionEventBuffer {
+  private static readonly STRIDE = 5;
+
+  private data: Float64Array;
+  private capacity: number;
+  private start = 0;
+  private length = 0;
+  dropped = 0;
+
+  constructor(capacity: number) {
+    this.capacity = capacity;
+    this.data = new Float64Array(capacity * CollisionEventBuffer.STRIDE);
+  }
+
+  getCapacity(): number {
+    return this.capacity;
+  }
+
+  getLength(): number {
+    return this.length;
+  }
+
+  setCapacity(capacity: number): void {
+    if (!Number.isSafeInteger(capacity) || capacity < 0) {
+      throw new Error('Event capacity must be a non-negative integer.');
+    }
+
+    this.capacity = capacity;
+    this.data = new Float64Array(capacity * CollisionEventBuffer.STRIDE);
+    this.clear();
+  }
+
+  push(idA: number, idB: number, x: number, y: number, speed: number): void {
+    const capacity = this.capacity;
+    if (capacity === 0) {
+      this.dropped++;
+      return;
+    }
+
+    let slot: number;
+    if (this.length === capacity) {
+      slot = this.start;
+      this.start = (this.start + 1) % capacity;
+      this.dropped++;
+    } else {
+      slot = (this.start + this.length) % capacity;
+      this.length++;
+    }
+
+    const offset = slot * CollisionEventBuffer.STRIDE;
+    const data = this.data;
+    data[offset] = idA;
+    data[offset + 1] = idB;
+    data[offset + 2] = x;
+    data[offset + 3] = y;
+    data[offset + 4] = speed;
+  }
+
+  drain(): CollisionEvent[] {
+    const events: CollisionEvent[] = [];
+    const data = this.data;
+    for (let i = 0; i < this.length; i++) {
+      const offset = ((this.start + i) % this.capacity) * CollisionEventBuffer.STRIDE;
+      events.push({
+        idA: data[offset],
+        idB: data[offset + 1],
+        x: data[offset + 2],
+        y: data[offset + 3],
+        speed: data[offset + 4],
+      });
+    }
+
+    this.clear();
+    return events;
+  }
+
+  clear(): void {
+    this.start = 0;
+    this.length = 0;
+  }
+}
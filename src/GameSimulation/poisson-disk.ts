@@ -0,0 +1,91 @@
+export interface Point {
+  x: number;
+  y: number;
+}
+
+export function samplePoissonDisk(
+  minX: number,
+  minY: number,
+  maxX: number,
+  maxY: number,
+  minDistance: number,
+  maxPoints: number,
+  random: () => number,
+  attempts = 30
+): Point[] {
+  const points: Point[] = [];
+  const width = maxX - minX;
+  const height = maxY - minY;
+  if (maxPoints <= 0 || width <= 0 || height <= 0 || minDistance <= 0) {
+    return points;
+  }
+
+  const cellSize = minDistance / Math.SQRT2;
+  const cols = Math.max(1, Math.ceil(width / cellSize));
+  const rows = Math.max(1, Math.ceil(height / cellSize));
+  const grid = new Int32Array(cols * rows).fill(-1);
+  const active: number[] = [];
+  const minDistance2 = minDistance * minDistance;
+
+  const toCol = (x: number): number => Math.min(Math.floor((x - minX) / cellSize), cols - 1);
+  const toRow = (y: number): number => Math.min(Math.floor((y - minY) / cellSize), rows - 1);
+
+  const addPoint = (x: number, y: number): void => {
+    const index = points.length;
+    points.push({ x, y });
+    active.push(index);
+    grid[toRow(y) * cols + toCol(x)] = index;
+  };
+
+  const isFarEnough = (x: number, y: number): boolean => {
+    const col = toCol(x);
+    const row = toRow(y);
+    const minCol = Math.max(col - 2, 0);
+    const maxCol = Math.min(col + 2, cols - 1);
+    const minRow = Math.max(row - 2, 0);
+    const maxRow = Math.min(row + 2, rows - 1);
+
+    for (let r = minRow; r <= maxRow; r++) {
+      for (let c = minCol; c <= maxCol; c++) {
+        const index = grid[r * cols + c];
+        if (index === -1) continue;
+
+        const dx = points[index].x - x;
+        const dy = points[index].y - y;
+        if (dx * dx + dy * dy < minDistance2) {
+          return false;
+        }
+      }
+    }
+    return true;
+  };
+
+  addPoint(minX + random() * width, minY + random() * height);
+
+  while (active.length > 0 && points.length < maxPoints) {
+    const activeIndex = Math.floor(random() * active.length);
+    const origin = points[active[activeIndex]];
+    let placed = false;
+
+    for (let attempt = 0; attempt < attempts; attempt++) {
+      const angle = random() * Math.PI * 2;
+      const distance = minDistance * (1 + random());
+      const x = origin.x + Math.cos(angle) * distance;
+      const y = origin.y + Math.sin(angle) * distance;
+
+      if (x < minX || x >= maxX || y < minY || y >= maxY) continue;
+      if (!isFarEnough(x, y)) continue;
+
+      addPoint(x, y);
+      placed = true;
+      break;
+    }
+
+    if (!placed) {
+      active[activeIndex] = active[active.length - 1];
+      active.pop();
+    }
+  }
+
+  return points;
+}
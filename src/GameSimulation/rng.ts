@@ -0,0 +1,106 @@
+export type RngKind = 'lcg' | 'xoshiro128';
+
+export abstract class Rng {
+  abstract readonly kind: RngKind;
+
+  abstract nextU32(): number;
+
+  abstract clone(): Rng;
+
+  nextFloat(): number {
+    return this.nextU32() / 0x100000000;
+  }
+
+  nextRange(min: number, max: number): number {
+    return min + (max - min) * this.nextFloat();
+  }
+
+  nextBool(): boolean {
+    return (this.nextU32() & 0x80000000) !== 0;
+  }
+}
+
+export class Lcg extends Rng {
+  readonly kind = 'lcg';
+  private state: number;
+
+  constructor(seed: number) {
+    super();
+    this.state = seed >>> 0;
+  }
+
+  nextU32(): number {
+    this.state = (Math.imul(this.state, 1664525) + 1013904223) >>> 0;
+    return this.state;
+  }
+
+  clone(): Lcg {
+    const cloned = new Lcg(0);
+    cloned.state = this.state;
+    return cloned;
+  }
+}
+
+export class Xoshiro128 extends Rng {
+  readonly kind = 'xoshiro128';
+  private s0: number;
+  private s1: number;
+  private s2: number;
+  private s3: number;
+
+  constructor(seed: number) {
+    super();
+    let state = seed >>> 0;
+    const splitMix32 = (): number => {
+      state = (state + 0x9e3779b9) | 0;
+      let z = state;
+      z = Math.imul(z ^ (z >>> 16), 0x85ebca6b);
+      z = Math.imul(z ^ (z >>> 13), 0xc2b2ae35);
+      return (z ^ (z >>> 16)) >>> 0;
+    };
+    this.s0 = splitMix32();
+    this.s1 = splitMix32();
+    this.s2 = splitMix32();
+    this.s3 = splitMix32();
+  }
+
+  nextU32(): number {
+    const result = Math.imul(rotateLeft(Math.imul(this.s1, 5), 7), 9) >>> 0;
+    const t = this.s1 << 9;
+
+    this.s2 ^= this.s0;
+    this.s3 ^= this.s1;
+    this.s1 ^= this.s2;
+    this.s0 ^= this.s3;
+    this.s2 ^= t;
+    this.s3 = rotateLeft(this.s3, 11);
+
+    return result;
+  }
+
+  clone(): Xoshiro128 {
+    const cloned = new Xoshiro128(0);
+    cloned.s0 = this.s0;
+    cloned.s1 = this.s1;
+    cloned.s2 = this.s2;
+    cloned.s3 = this.s3;
+    return cloned;
+  }
+}
+
+export function createRng(kind: RngKind, seed: number): Rng {
+  switch (kind) {
+    case 'lcg':
+      return new Lcg(seed);
+    case 'xoshiro128':
+      return new Xoshiro128(seed);
+    default: {
+      const exhaustiveCheck: never = kind;
+      throw new Error(`Unhandled RngKind: ${JSON.stringify(exhaustiveCheck)}`);
+    }
+  }
+}
+
+function rotateLeft(value: number, shift: number): number {
+  return (value << shift) | (value >>> (32 - shift));
+}
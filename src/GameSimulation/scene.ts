@@ -0,0 +1,60 @@
+export interface SceneBody {
+  x: number;
+  y: number;
+  vx: number;
+  vy: number;
+  radius: number;
+}
+
+export interface SceneDescription {
+  width: number;
+  height: number;
+  cellSize?: number;
+  bodies: SceneBody[];
+}
+
+export function parseScene(json: string): SceneDescription {
+  const data: unknown = JSON.parse(json);
+  if (!isRecord(data)) {
+    throw new Error('Scene must be a JSON object.');
+  }
+
+  const bodies = data.bodies ?? [];
+  if (!Array.isArray(bodies)) {
+    throw new Error('Scene field "bodies" must be an array.');
+  }
+
+  return {
+    width: readNumber(data, 'width'),
+    height: readNumber(data, 'height'),
+    cellSize: data.cellSize === undefined ? undefined : readNumber(data, 'cellSize'),
+    bodies: bodies.map((value: unknown, index: number) => {
+      if (!isRecord(value)) {
+        throw new Error(`Scene body ${index} must be an object.`);
+      }
+
+      return {
+        x: readNumber(value, 'x'),
+        y: readNumber(value, 'y'),
+        vx: readNumber(value, 'vx', 0),
+        vy: readNumber(value, 'vy', 0),
+        radius: readNumber(value, 'radius'),
+      };
+    }),
+  };
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+function readNumber(record: Record<string, unknown>, key: string, fallback?: number): number {
+  const value = record[key];
+  if (value === undefined && fallback !== undefined) {
+    return fallback;
+  }
+  if (typeof value !== 'number' || !Number.isFinite(value)) {
+    throw new Error(`Scene field "${key}" must be a finite number.`);
+  }
+  return value;
+}
@@ -0,0 +1,92 @@
+import { Entity } from './components.js';
+
+export interface EntityRecord {
+  id: number;
+  x: number;
+  y: number;
+  vx: number;
+  vy: number;
+  radius: number;
+}
+
+export interface SnapshotDelta {
+  added: EntityRecord[];
+  removed: number[];
+  modified: EntityRecord[];
+}
+
+export function takeSnapshot(entities: readonly Entity[]): EntityRecord[] {
+  const records: EntityRecord[] = [];
+  for (const entity of entities) {
+    const body = entity.body;
+    if (!body) continue;
+
+    records.push({
+      id: entity.id,
+      x: body.x,
+      y: body.y,
+      vx: body.vx,
+      vy: body.vy,
+      radius: body.radius,
+    });
+  }
+  return records;
+}
+
+export function diffSnapshots(
+  previous: readonly EntityRecord[],
+  next: readonly EntityRecord[]
+): SnapshotDelta {
+  const previousById = new Map<number, EntityRecord>();
+  for (const record of previous) {
+    previousById.set(record.id, record);
+  }
+
+  const delta: SnapshotDelta = { added: [], removed: [], modified: [] };
+  const nextIds = new Set<number>();
+  for (const record of next) {
+    nextIds.add(record.id);
+    const before = previousById.get(record.id);
+    if (!before) {
+      delta.added.push({ ...record });
+    } else if (!recordsEqual(before, record)) {
+      delta.modified.push({ ...record });
+    }
+  }
+
+  for (const record of previous) {
+    if (!nextIds.has(record.id)) {
+      delta.removed.push(record.id);
+    }
+  }
+
+  return delta;
+}
+
+export function applyDelta(
+  previous: readonly EntityRecord[],
+  delta: SnapshotDelta
+): EntityRecord[] {
+  const removed = new Set(delta.removed);
+  const modified = new Map<number, EntityRecord>();
+  for (const record of delta.modified) {
+    modified.set(record.id, record);
+  }
+
+  const result: EntityRecord[] = [];
+  for (const record of previous) {
+    if (removed.has(record.id)) continue;
+
+    result.push({ ...(modified.get(record.id) ?? record) });
+  }
+  for (const record of delta.added) {
+    result.push({ ...record });
+  }
+  return result;
+}
+
+function recordsEqual(a: EntityRecord, b: EntityRecord): boolean {
+  return (
+    a.x === b.x && a.y === b.y && a.vx === b.vx && a.vy === b.vy && a.radius === b.radius
+  );
+}
@@ -1,6 +1,26 @@
 import { Entity } from './components.js';
 
+export type GridMode = 'rebuild' | 'incremental';
+
+export interface CellCoordinates {
+  col: number;
+  row: number;
+  clampedCol: number;
+  clampedRow: number;
+}
+
+interface TrackedEntity {
+  entity: Entity;
+  minCol: number;
+  maxCol: number;
+  minRow: number;
+  maxRow: number;
+  seen: number;
+}
+
 interface Cell {
+  col: number;
+  row: number;
   items: Entity[];
   stamp: number;
 }
@@ -12,8 +32,14 @@ export class SpatialGrid {
   private width: number;
   private height: number;
   private stamp: number;
+  private activeCells: Cell[] = [];
+  private scratchCells: Cell[] = [];
+  private mode: GridMode = 'rebuild';
+  private tracked = new Map<number, TrackedEntity>();
+  private syncStamp = 0;
+  private lastVisitedCells = 0;
 
-  private static readonly OFFSET = 1 << 15;
+  private static readonly KEY_RANGE = 1 << 25;
 
   constructor(width: number, height: number, cellSize: number) {
     this.width = width;
@@ -32,9 +58,25 @@ export class SpatialGrid {
     this.cellSize = cellSize;
     this.cellSizeInv = 1 / cellSize;
     this.cells.clear();
+    this.activeCells.length = 0;
+    this.tracked.clear();
+    this.stamp = 1;
+  }
+
+  setMode(mode: GridMode): void {
+    if (mode === this.mode) return;
+
+    this.mode = mode;
+    this.cells.clear();
+    this.activeCells.length = 0;
+    this.tracked.clear();
     this.stamp = 1;
   }
 
+  getMode(): GridMode {
+    return this.mode;
+  }
+
   getCellSize(): number {
     return this.cellSize;
   }
@@ -45,8 +87,70 @@ export class SpatialGrid {
     return cols * rows;
   }
 
+  pointToCell(x: number, y: number): CellCoordinates {
+    const col = Math.floor(x * this.cellSizeInv);
+    const row = Math.floor(y * this.cellSizeInv);
+    const maxCol = Math.max(Math.ceil(this.width / this.cellSize) - 1, 0);
+    const maxRow = Math.max(Math.ceil(this.height / this.cellSize) - 1, 0);
+    return {
+      col,
+      row,
+      clampedCol: Math.min(Math.max(col, 0), maxCol),
+      clampedRow: Math.min(Math.max(row, 0), maxRow),
+    };
+  }
+
+  estimateMemoryUsage(pointerBytes: number, objectHeaderBytes: number): number {
+    let retainedItems = 0;
+    for (const cell of this.cells.values()) {
+      retainedItems += cell.items.length;
+    }
+
+    const mapEntryBytes = 3 * pointerBytes;
+    const cellBytes = objectHeaderBytes * 2 + 4 * pointerBytes;
+    const trackedBytes = objectHeaderBytes + 6 * pointerBytes;
+    return (
+      this.cells.size * (mapEntryBytes + cellBytes) +
+      this.tracked.size * (mapEntryBytes + trackedBytes) +
+      retainedItems * pointerBytes
+    );
+  }
+
+  getLastVisitedCellCount(): number {
+    return this.lastVisitedCells;
+  }
+
+  getActiveCellCount(): number {
+    return this.activeCells.length;
+  }
+
+  forEachActiveCell(visit: (col: number, row: number, items: readonly Entity[]) => void): void {
+    const cells = this.activeCells;
+    for (let i = 0; i < cells.length; i++) {
+      const cell = cells[i];
+      visit(cell.col, cell.row, cell.items);
+    }
+  }
+
+  compact(): void {
+    if (this.mode === 'incremental') return;
+
+    for (const [key, cell] of this.cells) {
+      if (cell.stamp !== this.stamp) {
+        this.cells.delete(key);
+      }
+    }
+    this.scratchCells = [];
+  }
+
   clear(): void {
+    if (this.mode === 'incremental') {
+      this.syncStamp++;
+      return;
+    }
+
     this.stamp++;
+    this.activeCells.length = 0;
     if (this.stamp === Number.MAX_SAFE_INTEGER) {
       this.cells.clear();
       this.stamp = 1;
@@ -63,23 +167,45 @@ export class SpatialGrid {
     const minRow = Math.floor((body.y - radius) * this.cellSizeInv);
     const maxRow = Math.floor((body.y + radius) * this.cellSizeInv);
 
+    if (this.mode === 'incremental') {
+      this.syncEntity(entity, minCol, maxCol, minRow, maxRow);
+      return;
+    }
+
     for (let col = minCol; col <= maxCol; col++) {
       for (let row = minRow; row <= maxRow; row++) {
         const key = this.packKey(col, row);
         let cell = this.cells.get(key);
         if (!cell) {
-          cell = { items: [], stamp: 0 };
+          cell = { col, row, items: [], stamp: 0 };
           this.cells.set(key, cell);
         }
         if (cell.stamp !== this.stamp) {
           cell.items.length = 0;
           cell.stamp = this.stamp;
+          this.activeCells.push(cell);
         }
         cell.items.push(entity);
       }
     }
   }
 
+  prune(): void {
+    if (this.mode !== 'incremental') return;
+
+    for (const [id, entry] of this.tracked) {
+      if (entry.seen !== this.syncStamp) {
+        this.removeTracked(entry);
+        this.tracked.delete(id);
+      }
+    }
+
+    this.activeCells.length = 0;
+    for (const cell of this.cells.values()) {
+      this.activeCells.push(cell);
+    }
+  }
+
   query(entity: Entity): Entity[] {
     const body = entity.body;
     if (!body) return [];
@@ -93,21 +219,16 @@ export class SpatialGrid {
     const seenIds = new Set<number>();
     const result: Entity[] = [];
 
-    for (let col = minCol; col <= maxCol; col++) {
-      for (let row = minRow; row <= maxRow; row++) {
-        const key = this.packKey(col, row);
-        const cell = this.cells.get(key);
-        if (!cell || cell.stamp !== this.stamp) continue;
-
-        const items = cell.items;
-        for (let i = 0; i < items.length; i++) {
-          const other = items[i];
-          if (other === entity) continue;
-          const id = other.id;
-          if (!seenIds.has(id)) {
-            seenIds.add(id);
-            result.push(other);
-          }
+    const cells = this.gatherCells(minCol, maxCol, minRow, maxRow);
+    for (let c = 0; c < cells.length; c++) {
+      const items = cells[c].items;
+      for (let i = 0; i < items.length; i++) {
+        const other = items[i];
+        if (other === entity) continue;
+        const id = other.id;
+        if (!seenIds.has(id)) {
+          seenIds.add(id);
+          result.push(other);
         }
       }
     }
@@ -115,7 +236,33 @@ export class SpatialGrid {
     return result;
   }
 
+  overlapsAny(x: number, y: number, radius: number): boolean {
+    const minCol = Math.floor((x - radius) * this.cellSizeInv) - 1;
+    const maxCol = Math.floor((x + radius) * this.cellSizeInv) + 1;
+    const minRow = Math.floor((y - radius) * this.cellSizeInv) - 1;
+    const maxRow = Math.floor((y + radius) * this.cellSizeInv) + 1;
+
+    const cells = this.gatherCells(minCol, maxCol, minRow, maxRow);
+    for (let c = 0; c < cells.length; c++) {
+      const items = cells[c].items;
+      for (let i = 0; i < items.length; i++) {
+        const b = items[i].body;
+        if (!b) continue;
+
+        const dx = b.x - x;
+        const dy = b.y - y;
+        const minDist = b.radius + radius;
+        if (dx * dx + dy * dy < minDist * minDist) {
+          return true;
+        }
+      }
+    }
+
+    return false;
+  }
+
   getEntitiesInRadius(x: number, y: number, radius: number): Entity[] {
+    const seenIds = new Set<number>();
     const result: Entity[] = [];
     const r2 = radius * radius;
 
@@ -124,33 +271,469 @@ export class SpatialGrid {
     const minRow = Math.floor((y - radius) * this.cellSizeInv);
     const maxRow = Math.floor((y + radius) * this.cellSizeInv);
 
-    for (let col = minCol; col <= maxCol; col++) {
-      for (let row = minRow; row <= maxRow; row++) {
-        const key = this.packKey(col, row);
-        const cell = this.cells.get(key);
-        if (!cell || cell.stamp !== this.stamp) continue;
+    const cells = this.gatherCells(minCol, maxCol, minRow, maxRow);
+    for (let c = 0; c < cells.length; c++) {
+      const items = cells[c].items;
+      for (let i = 0; i < items.length; i++) {
+        const e = items[i];
+        const b = e.body;
+        if (!b || seenIds.has(e.id)) continue;
+
+        const dx = b.x - x;
+        const dy = b.y - y;
+        if (dx * dx + dy * dy <= r2) {
+          seenIds.add(e.id);
+          result.push(e);
+        }
+      }
+    }
+
+    return result;
+  }
+
+  countEntitiesInRadius(x: number, y: number, radius: number): number {
+    const seenIds = new Set<number>();
+    const r2 = radius * radius;
 
+    const minCol = Math.floor((x - radius) * this.cellSizeInv);
+    const maxCol = Math.floor((x + radius) * this.cellSizeInv);
+    const minRow = Math.floor((y - radius) * this.cellSizeInv);
+    const maxRow = Math.floor((y + radius) * this.cellSizeInv);
+
+    const cells = this.gatherCells(minCol, maxCol, minRow, maxRow);
+    for (let c = 0; c < cells.length; c++) {
+      const items = cells[c].items;
+      for (let i = 0; i < items.length; i++) {
+        const e = items[i];
+        const b = e.body;
+        if (!b || seenIds.has(e.id)) continue;
+
+        const dx = b.x - x;
+        const dy = b.y - y;
+        if (dx * dx + dy * dy <= r2) {
+          seenIds.add(e.id);
+        }
+      }
+    }
+
+    return seenIds.size;
+  }
+
+  nearest(x: number, y: number, k: number): { entity: Entity; distance: number }[] {
+    const active = this.activeCells;
+    if (k <= 0 || active.length === 0) return [];
+
+    const col = Math.floor(x * this.cellSizeInv);
+    const row = Math.floor(y * this.cellSizeInv);
+    let maxRing = 0;
+    for (let i = 0; i < active.length; i++) {
+      const cell = active[i];
+      maxRing = Math.max(maxRing, Math.abs(cell.col - col), Math.abs(cell.row - row));
+    }
+
+    const seenIds = new Set<number>();
+    const found: { entity: Entity; distance: number }[] = [];
+    const visit = (c: number, r: number): void => {
+      const cell = this.cells.get(this.packKey(c, r));
+      if (!cell || cell.stamp !== this.stamp) return;
+
+      const items = cell.items;
+      for (let i = 0; i < items.length; i++) {
+        const e = items[i];
+        const b = e.body;
+        if (!b || seenIds.has(e.id)) continue;
+
+        seenIds.add(e.id);
+        const dx = b.x - x;
+        const dy = b.y - y;
+        found.push({ entity: e, distance: Math.sqrt(dx * dx + dy * dy) });
+      }
+    };
+
+    for (let ring = 0; ring <= maxRing; ring++) {
+      if (ring === 0) {
+        visit(col, row);
+      } else {
+        for (let c = col - ring; c <= col + ring; c++) {
+          visit(c, row - ring);
+          visit(c, row + ring);
+        }
+        for (let r = row - ring + 1; r <= row + ring - 1; r++) {
+          visit(col - ring, r);
+          visit(col + ring, r);
+        }
+      }
+
+      if (found.length >= k) {
+        found.sort((a, b) => a.distance - b.distance);
+        if (found[k - 1].distance <= ring * this.cellSize) break;
+      }
+    }
+
+    found.sort((a, b) => a.distance - b.distance);
+    return found.slice(0, k);
+  }
+
+  getEntitiesAtPoint(x: number, y: number): readonly Entity[] {
+    return this.getCellContents(Math.floor(x * this.cellSizeInv), Math.floor(y * this.cellSizeInv));
+  }
+
+  getCellContents(col: number, row: number): readonly Entity[] {
+    const cell = this.cells.get(this.packKey(col, row));
+    return cell && cell.stamp === this.stamp ? cell.items : [];
+  }
+
+  raycast(
+    originX: number,
+    originY: number,
+    dirX: number,
+    dirY: number,
+    maxDistance: number
+  ): { entity: Entity; distance: number } | null {
+    this.lastVisitedCells = 0;
+    const length = Math.sqrt(dirX * dirX + dirY * dirY);
+    if (length === 0 || !(maxDistance > 0) || !Number.isFinite(maxDistance)) {
+      return null;
+    }
+
+    const bounds = this.occupiedBounds();
+    if (!bounds) return null;
+
+    const dx = dirX / length;
+    const dy = dirY / length;
+    const cellSize = this.cellSize;
+    const span = this.clipLine(
+      originX,
+      originY,
+      dx,
+      dy,
+      bounds.minCol * cellSize,
+      bounds.minRow * cellSize,
+      (bounds.maxCol + 1) * cellSize,
+      (bounds.maxRow + 1) * cellSize
+    );
+    if (!span) return null;
+
+    const tStart = Math.max(span.enter, 0);
+    const tEnd = Math.min(span.exit, maxDistance);
+    if (tStart > tEnd) return null;
+
+    const startX = originX + dx * tStart;
+    const startY = originY + dy * tStart;
+    let col = Math.floor(startX * this.cellSizeInv);
+    let row = Math.floor(startY * this.cellSizeInv);
+    col = Math.min(Math.max(col, bounds.minCol), bounds.maxCol);
+    row = Math.min(Math.max(row, bounds.minRow), bounds.maxRow);
+    const stepCol = dx > 0 ? 1 : -1;
+    const stepRow = dy > 0 ? 1 : -1;
+    const tDeltaX = dx !== 0 ? cellSize / Math.abs(dx) : Infinity;
+    const tDeltaY = dy !== 0 ? cellSize / Math.abs(dy) : Infinity;
+    let tMaxX = dx !== 0 ? ((dx > 0 ? col + 1 : col) * cellSize - originX) / dx : Infinity;
+    let tMaxY = dy !== 0 ? ((dy > 0 ? row + 1 : row) * cellSize - originY) / dy : Infinity;
+
+    let best: Entity | null = null;
+    let bestDistance = maxDistance;
+    let tEntry = tStart;
+
+    while (tEntry <= bestDistance && tEntry <= tEnd) {
+      this.lastVisitedCells++;
+      const cell = this.cells.get(this.packKey(col, row));
+      if (cell && cell.stamp === this.stamp) {
         const items = cell.items;
         for (let i = 0; i < items.length; i++) {
           const e = items[i];
           const b = e.body;
           if (!b) continue;
 
-          const dx = b.x - x;
-          const dy = b.y - y;
-          if (dx * dx + dy * dy <= r2) {
-            result.push(e);
+          const fx = originX - b.x;
+          const fy = originY - b.y;
+          const c = fx * fx + fy * fy - b.radius * b.radius;
+          let t: number;
+          if (c <= 0) {
+            t = 0;
+          } else {
+            const proj = fx * dx + fy * dy;
+            const discriminant = proj * proj - c;
+            if (proj > 0 || discriminant < 0) continue;
+            t = -proj - Math.sqrt(discriminant);
+          }
+
+          if (t < bestDistance || (best === null && t <= bestDistance)) {
+            best = e;
+            bestDistance = t;
           }
         }
       }
+
+      if (tMaxX < tMaxY) {
+        tEntry = tMaxX;
+        tMaxX += tDeltaX;
+        col += stepCol;
+      } else {
+        tEntry = tMaxY;
+        tMaxY += tDeltaY;
+        row += stepRow;
+      }
+    }
+
+    return best ? { entity: best, distance: bestDistance } : null;
+  }
+
+  queryCapsule(x0: number, y0: number, x1: number, y1: number, radius: number): Entity[] {
+    this.lastVisitedCells = 0;
+    const result: Entity[] = [];
+    const bounds = this.occupiedBounds();
+    if (!(radius >= 0) || !Number.isFinite(radius) || !bounds) return result;
+
+    const segX = x1 - x0;
+    const segY = y1 - y0;
+    const length2 = segX * segX + segY * segY;
+    if (!Number.isFinite(length2) || !Number.isFinite(x0) || !Number.isFinite(y0)) return result;
+
+    const seenIds = new Set<number>();
+    const testItems = (items: readonly Entity[]): void => {
+      for (let i = 0; i < items.length; i++) {
+        const e = items[i];
+        const b = e.body;
+        if (!b || seenIds.has(e.id)) continue;
+        seenIds.add(e.id);
+
+        const projection = length2 > 0 ? ((b.x - x0) * segX + (b.y - y0) * segY) / length2 : 0;
+        const t = Math.min(Math.max(projection, 0), 1);
+        const dx = b.x - (x0 + segX * t);
+        const dy = b.y - (y0 + segY * t);
+        const reach = radius + b.radius;
+        if (dx * dx + dy * dy <= reach * reach) {
+          result.push(e);
+        }
+      }
+    };
+
+    const length = Math.sqrt(length2);
+    const dx = length > 0 ? segX / length : 0;
+    const dy = length > 0 ? segY / length : 0;
+    const cellSize = this.cellSize;
+    const span = this.clipLine(
+      x0,
+      y0,
+      dx,
+      dy,
+      bounds.minCol * cellSize - radius,
+      bounds.minRow * cellSize - radius,
+      (bounds.maxCol + 1) * cellSize + radius,
+      (bounds.maxRow + 1) * cellSize + radius
+    );
+    if (!span) return result;
+
+    const tStart = Math.max(span.enter, 0);
+    const tEnd = Math.min(span.exit, length);
+    if (tStart > tEnd) return result;
+
+    let col = Math.floor((x0 + dx * tStart) * this.cellSizeInv);
+    let row = Math.floor((y0 + dy * tStart) * this.cellSizeInv);
+    const endCol = Math.floor((x0 + dx * tEnd) * this.cellSizeInv);
+    const endRow = Math.floor((y0 + dy * tEnd) * this.cellSizeInv);
+    const pad = Math.ceil(radius * this.cellSizeInv);
+    const walkCells = (Math.abs(endCol - col) + Math.abs(endRow - row) + 1) * (2 * pad + 1) ** 2;
+    if (walkCells > this.activeCells.length) {
+      const active = this.activeCells;
+      for (let i = 0; i < active.length; i++) {
+        testItems(active[i].items);
+      }
+      this.lastVisitedCells = active.length;
+      return result;
+    }
+
+    const seenCells = new Set<number>();
+    const visit = (centerCol: number, centerRow: number): void => {
+      for (let c = centerCol - pad; c <= centerCol + pad; c++) {
+        for (let r = centerRow - pad; r <= centerRow + pad; r++) {
+          const key = this.packKey(c, r);
+          if (seenCells.has(key)) continue;
+          seenCells.add(key);
+          this.lastVisitedCells++;
+
+          const cell = this.cells.get(key);
+          if (cell && cell.stamp === this.stamp) {
+            testItems(cell.items);
+          }
+        }
+      }
+    };
+
+    visit(col, row);
+    if (tEnd === tStart) return result;
+
+    const stepCol = dx > 0 ? 1 : -1;
+    const stepRow = dy > 0 ? 1 : -1;
+    const tDeltaX = dx !== 0 ? cellSize / Math.abs(dx) : Infinity;
+    const tDeltaY = dy !== 0 ? cellSize / Math.abs(dy) : Infinity;
+    let tMaxX = dx !== 0 ? ((dx > 0 ? col + 1 : col) * cellSize - x0) / dx : Infinity;
+    let tMaxY = dy !== 0 ? ((dy > 0 ? row + 1 : row) * cellSize - y0) / dy : Infinity;
+
+    while (Math.min(tMaxX, tMaxY) <= tEnd) {
+      if (tMaxX < tMaxY) {
+        tMaxX += tDeltaX;
+        col += stepCol;
+      } else {
+        tMaxY += tDeltaY;
+        row += stepRow;
+      }
+      visit(col, row);
+    }
+
+    return result;
+  }
+
+  private syncEntity(
+    entity: Entity,
+    minCol: number,
+    maxCol: number,
+    minRow: number,
+    maxRow: number
+  ): void {
+    let entry = this.tracked.get(entity.id);
+    if (
+      entry &&
+      entry.entity === entity &&
+      entry.minCol === minCol &&
+      entry.maxCol === maxCol &&
+      entry.minRow === minRow &&
+      entry.maxRow === maxRow
+    ) {
+      entry.seen = this.syncStamp;
+      return;
+    }
+
+    if (entry) {
+      this.removeTracked(entry);
+      entry.entity = entity;
+      entry.minCol = minCol;
+      entry.maxCol = maxCol;
+      entry.minRow = minRow;
+      entry.maxRow = maxRow;
+    } else {
+      entry = { entity, minCol, maxCol, minRow, maxRow, seen: 0 };
+      this.tracked.set(entity.id, entry);
+    }
+    entry.seen = this.syncStamp;
+
+    for (let col = minCol; col <= maxCol; col++) {
+      for (let row = minRow; row <= maxRow; row++) {
+        const key = this.packKey(col, row);
+        let cell = this.cells.get(key);
+        if (!cell) {
+          cell = { col, row, items: [], stamp: this.stamp };
+          this.cells.set(key, cell);
+          this.activeCells.push(cell);
+        }
+        cell.items.push(entity);
+      }
     }
+  }
 
+  private removeTracked(entry: TrackedEntity): void {
+    for (let col = entry.minCol; col <= entry.maxCol; col++) {
+      for (let row = entry.minRow; row <= entry.maxRow; row++) {
+        const key = this.packKey(col, row);
+        const cell = this.cells.get(key);
+        if (!cell) continue;
+
+        const items = cell.items;
+        const index = items.indexOf(entry.entity);
+        if (index === -1) continue;
+
+        items[index] = items[items.length - 1];
+        items.pop();
+        if (items.length === 0) {
+          this.cells.delete(key);
+        }
+      }
+    }
+  }
+
+  private gatherCells(minCol: number, maxCol: number, minRow: number, maxRow: number): Cell[] {
+    const result = this.scratchCells;
+    result.length = 0;
+
+    const span = (maxCol - minCol + 1) * (maxRow - minRow + 1);
+    if (span > this.activeCells.length) {
+      const active = this.activeCells;
+      for (let i = 0; i < active.length; i++) {
+        const cell = active[i];
+        if (cell.col >= minCol && cell.col <= maxCol && cell.row >= minRow && cell.row <= maxRow) {
+          result.push(cell);
+        }
+      }
+      return result;
+    }
+
+    for (let col = minCol; col <= maxCol; col++) {
+      for (let row = minRow; row <= maxRow; row++) {
+        const cell = this.cells.get(this.packKey(col, row));
+        if (cell && cell.stamp === this.stamp) {
+          result.push(cell);
+        }
+      }
+    }
     return result;
   }
 
+  private occupiedBounds(): {
+    minCol: number;
+    maxCol: number;
+    minRow: number;
+    maxRow: number;
+  } | null {
+    const active = this.activeCells;
+    if (active.length === 0) return null;
+
+    let minCol = Infinity;
+    let maxCol = -Infinity;
+    let minRow = Infinity;
+    let maxRow = -Infinity;
+    for (let i = 0; i < active.length; i++) {
+      const cell = active[i];
+      minCol = Math.min(minCol, cell.col);
+      maxCol = Math.max(maxCol, cell.col);
+      minRow = Math.min(minRow, cell.row);
+      maxRow = Math.max(maxRow, cell.row);
+    }
+    return { minCol, maxCol, minRow, maxRow };
+  }
+
+  private clipLine(
+    originX: number,
+    originY: number,
+    dirX: number,
+    dirY: number,
+    minX: number,
+    minY: number,
+    maxX: number,
+    maxY: number
+  ): { enter: number; exit: number } | null {
+    let enter = -Infinity;
+    let exit = Infinity;
+    const clipAxis = (origin: number, dir: number, min: number, max: number): boolean => {
+      if (dir === 0) return origin >= min && origin <= max;
+
+      const t0 = (min - origin) / dir;
+      const t1 = (max - origin) / dir;
+      enter = Math.max(enter, Math.min(t0, t1));
+      exit = Math.min(exit, Math.max(t0, t1));
+      return enter <= exit;
+    };
+
+    if (!clipAxis(originX, dirX, minX, maxX) || !clipAxis(originY, dirY, minY, maxY)) {
+      return null;
+    }
+    return { enter, exit };
+  }
+
   private packKey(col: number, row: number): number {
-    const c = (col + SpatialGrid.OFFSET) & 0xffff;
-    const r = (row + SpatialGrid.OFFSET) & 0xffff;
-    return (c << 16) ^ r;
+    const range = SpatialGrid.KEY_RANGE;
+    const c = Math.min(Math.max(col, -range), range - 1) + range;
+    const r = Math.min(Math.max(row, -range), range - 1) + range;
+    return c * range * 2 + r;
   }
 }
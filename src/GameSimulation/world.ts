@@ -1,14 +1,290 @@
 import { Entity, Body, HP, Payload } from './components.js';
-import { SpatialGrid } from './spatial-grid.js';
+import { SpatialGrid, GridMode } from './spatial-grid.js';
+import { Rng, RngKind, createRng } from './rng.js';
+import { CollisionEventBuffer } from './events.js';
+
+export interface Emitter {
+  x: number;
+  y: number;
+  rate: number;
+  speed: number;
+  spread: number;
+  angle: number;
+  radius: number;
+  accumulator: number;
+}
+
+export type CollisionModel = 'impulse_elastic' | 'positional_only' | 'inelastic';
+
+export type Edge = 'left' | 'right' | 'top' | 'bottom';
+
+export type EdgeBehavior = 'bounce' | 'wrap' | 'kill' | 'open' | 'absorb';
+
+export interface Spring {
+  handle: number;
+  idA: number;
+  idB: number;
+  restLength: number;
+  stiffness: number;
+  damping: number;
+}
+
+export interface FlowField {
+  cols: number;
+  rows: number;
+  vectors: Float32Array;
+  strength: number;
+}
+
+export interface FrozenRegion {
+  minX: number;
+  minY: number;
+  maxX: number;
+  maxY: number;
+}
+
+export interface DebugContext {
+  frame: number;
+  seed: number;
+  entityIds: number[];
+}
+
+export interface Bounds {
+  minX: number;
+  minY: number;
+  maxX: number;
+  maxY: number;
+}
+
+export type Broadphase = 'grid' | 'brute_force' | 'auto';
+
+export type UpdatePhase = 'integrated' | 'solver_pass' | 'completed';
+
+export interface RaycastHit {
+  id: number;
+  distance: number;
+}
+
+export interface WorldConfig {
+  width: number;
+  height: number;
+  cellSize: number;
+  gridMode: GridMode;
+  twoLevelGrid: boolean;
+  broadphase: Broadphase;
+  bruteForceThreshold: number;
+  seed: number;
+  rngKind: RngKind;
+  entityCount: number;
+  entityBudget: number;
+  collisionModel: CollisionModel;
+  correctionSlop: number;
+  correctionPercent: number;
+  correctionDamping: number;
+  maxNeighborsPerBody: number;
+  contactPasses: number;
+  rotationalCollisions: boolean;
+  contactFriction: number;
+  wallRestitution: number;
+  bodyRestitution: number;
+  edgeBehaviors: Record<Edge, EdgeBehavior>;
+  boundsInset: number;
+  damping: number;
+  gravityX: number;
+  gravityY: number;
+  brownianStrength: number;
+  pointerGravity: { x: number; y: number; strength: number } | null;
+  flowField: Omit<FlowField, 'vectors'> | null;
+  frozenRegions: FrozenRegion[];
+  fastSpawn: boolean;
+  placementIgnoresExisting: boolean;
+  antiStick: boolean;
+  antiStickFrames: number;
+  mergeOnCollision: boolean;
+  fragmentSizeThreshold: number;
+  fragmentSpeedThreshold: number;
+  heatDecay: number;
+  eventCapacity: number;
+  debugChecks: boolean;
+}
 
 export class World {
+  static readonly MAX_ENTITIES = 10000;
+  static readonly COARSE_GRID_FACTOR = 8;
+  static readonly CONTACT_TOLERANCE = 1e-6;
+
   entities: Entity[] = [];
+  emitters: Emitter[] = [];
+  springs: Spring[] = [];
   width = 2500;
   height = 1200;
   spatialGrid: SpatialGrid;
+  coarseGrid: SpatialGrid;
+  twoLevelGrid = false;
+  broadphase: Broadphase = 'grid';
+  bruteForceThreshold = 64;
+  private gridStale = false;
+  readonly seed: number;
+  rng: Rng;
+  private placementRng: Rng;
+  frame = 0;
+  entityBudget = 0;
+  fastSpawn = false;
+  placementIgnoresExisting = false;
+  damping = 0;
+  gravityX = 0;
+  gravityY = 0;
+  brownianStrength = 0;
+  pointerGravity: { x: number; y: number; strength: number } | null = null;
+  flowField: FlowField | null = null;
+  frozenRegions: FrozenRegion[] = [];
+  private frozenBodies = new Set<Body>();
+  lastEvictedCount = 0;
+  antiStick = false;
+  antiStickFrames = 30;
+  antiStickFiredCount = 0;
+  private overlapStreaks = new Map<bigint, number>();
+  mergeOnCollision = false;
+  lastMergeCount = 0;
+  fragmentSizeThreshold = Infinity;
+  fragmentSpeedThreshold = Infinity;
+  lastFragmentCount = 0;
+  lastPairCheckCount = 0;
+  heatField = new Float32Array(0);
+  heatDecay = 0.9;
+  private heatColumns = 0;
+  collisionEvents = new CollisionEventBuffer(256);
+  debugChecks = false;
+  movementEpsilon = 0.01;
+  lastMaxDisplacement = 0;
+  movedIds: number[] = [];
+  private movementEntities: Entity[] = [];
+  private movementX = new Float64Array(0);
+  private movementY = new Float64Array(0);
+  contactPairs: number[] = [];
+  private entityIndex = new Map<number, number>();
+  private entityIndexDirty = true;
+  private nextSpringHandle = 1;
+  private nextEntityId = 1;
+  collisionModel: CollisionModel = 'impulse_elastic';
+  correctionSlop = 0;
+  correctionPercent = 1;
+  correctionDamping = 0;
+  maxNeighborsPerBody = 0;
+  contactPasses = 1;
+  rotationalCollisions = false;
+  contactFriction = 0.5;
+  wallRestitution = 1;
+  bodyRestitution = 1;
+  edgeBehaviors: Record<Edge, EdgeBehavior> = {
+    left: 'bounce',
+    right: 'bounce',
+    top: 'bounce',
+    bottom: 'bounce',
+  };
+  boundsInset = 0;
 
-  constructor() {
+  constructor(seed = Math.floor(Math.random() * 0x100000000), rngKind: RngKind = 'lcg') {
     this.spatialGrid = new SpatialGrid(this.width, this.height, 24);
+    this.coarseGrid = new SpatialGrid(this.width, this.height, 24 * World.COARSE_GRID_FACTOR);
+    this.seed = seed >>> 0;
+    this.rng = createRng(rngKind, this.seed);
+    this.placementRng = createRng(rngKind, this.placementSeed(0));
+  }
+
+  beginPlacement(stream: number): void {
+    if (!Number.isInteger(stream) || stream < 0) {
+      throw new Error('Placement stream must be a non-negative integer.');
+    }
+    this.placementRng = createRng(this.rng.kind, this.placementSeed(stream));
+  }
+
+  private placementSeed(stream: number): number {
+    return (this.seed ^ Math.imul(stream + 1, 0x9e3779b9)) >>> 0;
+  }
+
+  resize(width: number, height: number): void {
+    if (!(width > 0) || !(height > 0)) {
+      throw new Error('World dimensions must be greater than zero.');
+    }
+
+    const mode = this.getGridMode();
+    const cellSize = this.getGridCellSize();
+    this.width = width;
+    this.height = height;
+    this.spatialGrid = new SpatialGrid(width, height, cellSize);
+    this.spatialGrid.setMode(mode);
+    this.coarseGrid = new SpatialGrid(width, height, cellSize * World.COARSE_GRID_FACTOR);
+  }
+
+  setFlowField(cols: number, rows: number, vectors: ArrayLike<number>, strength: number): void {
+    if (!Number.isSafeInteger(cols) || !Number.isSafeInteger(rows) || cols <= 0 || rows <= 0) {
+      throw new Error('Flow field dimensions must be positive integers.');
+    }
+    if (vectors.length < cols * rows * 2) {
+      throw new Error('Flow field needs an (x, y) vector for every cell.');
+    }
+
+    const data = new Float32Array(cols * rows * 2);
+    for (let i = 0; i < data.length; i++) {
+      data[i] = vectors[i];
+    }
+    this.flowField = { cols, rows, vectors: data, strength: Math.max(strength, 0) };
+  }
+
+  setPointerGravity(x: number, y: number, strength: number): void {
+    if (strength === 0) {
+      this.pointerGravity = null;
+      return;
+    }
+
+    if (this.pointerGravity) {
+      this.pointerGravity.x = x;
+      this.pointerGravity.y = y;
+      this.pointerGravity.strength = strength;
+    } else {
+      this.pointerGravity = { x, y, strength };
+    }
+  }
+
+  clearFlowField(): void {
+    this.flowField = null;
+  }
+
+  addFrozenRegion(minX: number, minY: number, maxX: number, maxY: number): void {
+    if (!(maxX > minX) || !(maxY > minY)) {
+      throw new Error('Frozen region must have a positive width and height.');
+    }
+
+    this.frozenRegions.push({ minX, minY, maxX, maxY });
+  }
+
+  clearFrozenRegions(): void {
+    this.frozenRegions = [];
+  }
+
+  setWrap(axis: 'x' | 'y', enabled: boolean): void {
+    const behavior: EdgeBehavior = enabled ? 'wrap' : 'bounce';
+    if (axis === 'x') {
+      this.edgeBehaviors.left = behavior;
+      this.edgeBehaviors.right = behavior;
+    } else {
+      this.edgeBehaviors.top = behavior;
+      this.edgeBehaviors.bottom = behavior;
+    }
+  }
+
+  setBoundsInset(inset: number): void {
+    if (!(inset >= 0) || inset * 2 >= Math.min(this.width, this.height)) {
+      throw new Error('Bounds inset must be non-negative and leave a positive play area.');
+    }
+
+    this.boundsInset = inset;
+  }
+
+  getSpawnBounds(): Bounds {
+    const inset = this.boundsInset;
+    return { minX: inset, minY: inset, maxX: this.width - inset, maxY: this.height - inset };
   }
 
   getGridCellSize(): number {
@@ -24,26 +300,331 @@ export class World {
     const maxCellSize = Math.max(this.width, this.height);
     const clampedSize = Math.min(Math.max(cellSize, minCellSize), maxCellSize);
     this.spatialGrid.setCellSize(clampedSize);
+    this.coarseGrid.setCellSize(clampedSize * World.COARSE_GRID_FACTOR);
     return this.spatialGrid.getCellSize();
   }
 
+  getGridMode(): GridMode {
+    return this.spatialGrid.getMode();
+  }
+
+  setGridMode(mode: GridMode): void {
+    this.spatialGrid.setMode(mode);
+  }
+
   adjustGridCellSize(delta: number): number {
     const nextSize = this.spatialGrid.getCellSize() + delta;
     return this.setGridCellSize(nextSize);
   }
 
+  getConfig(): WorldConfig {
+    return {
+      width: this.width,
+      height: this.height,
+      cellSize: this.getGridCellSize(),
+      gridMode: this.getGridMode(),
+      twoLevelGrid: this.twoLevelGrid,
+      broadphase: this.broadphase,
+      bruteForceThreshold: this.bruteForceThreshold,
+      seed: this.seed,
+      rngKind: this.rng.kind,
+      entityCount: this.entities.length,
+      entityBudget: this.entityBudget,
+      collisionModel: this.collisionModel,
+      correctionSlop: this.correctionSlop,
+      correctionPercent: this.correctionPercent,
+      correctionDamping: this.correctionDamping,
+      maxNeighborsPerBody: this.maxNeighborsPerBody,
+      contactPasses: this.contactPasses,
+      rotationalCollisions: this.rotationalCollisions,
+      contactFriction: this.contactFriction,
+      wallRestitution: this.wallRestitution,
+      bodyRestitution: this.bodyRestitution,
+      edgeBehaviors: { ...this.edgeBehaviors },
+      boundsInset: this.boundsInset,
+      damping: this.damping,
+      gravityX: this.gravityX,
+      gravityY: this.gravityY,
+      brownianStrength: this.brownianStrength,
+      pointerGravity: this.pointerGravity ? { ...this.pointerGravity } : null,
+      flowField: this.flowField
+        ? {
+            cols: this.flowField.cols,
+            rows: this.flowField.rows,
+            strength: this.flowField.strength,
+          }
+        : null,
+      frozenRegions: this.frozenRegions.map((region) => ({ ...region })),
+      fastSpawn: this.fastSpawn,
+      placementIgnoresExisting: this.placementIgnoresExisting,
+      antiStick: this.antiStick,
+      antiStickFrames: this.antiStickFrames,
+      mergeOnCollision: this.mergeOnCollision,
+      fragmentSizeThreshold: this.fragmentSizeThreshold,
+      fragmentSpeedThreshold: this.fragmentSpeedThreshold,
+      heatDecay: this.heatDecay,
+      eventCapacity: this.collisionEvents.getCapacity(),
+      debugChecks: this.debugChecks,
+    };
+  }
+
+  estimateMemoryUsage(): number {
+    const pointerBytes = 8;
+    const numberBytes = 8;
+    const objectHeaderBytes = 16;
+
+    const estimateObject = (value: object): number =>
+      objectHeaderBytes + Object.keys(value).length * numberBytes;
+
+    let bytes = (this.entities.length + this.emitters.length) * pointerBytes;
+    for (const entity of this.entities) {
+      bytes += estimateObject(entity);
+      if (entity.body) bytes += estimateObject(entity.body);
+      if (entity.hp) bytes += estimateObject(entity.hp);
+      if (entity.payload) bytes += estimateObject(entity.payload) + entity.payload.type.length * 2;
+    }
+    for (const emitter of this.emitters) {
+      bytes += estimateObject(emitter);
+    }
+
+    return (
+      bytes +
+      this.spatialGrid.estimateMemoryUsage(pointerBytes, objectHeaderBytes) +
+      this.coarseGrid.estimateMemoryUsage(pointerBytes, objectHeaderBytes)
+    );
+  }
+
+  indexOfEntity(id: number): number {
+    if (this.entityIndexDirty) {
+      this.rebuildEntityIndex();
+    }
+
+    return this.entityIndex.get(id) ?? -1;
+  }
+
+  private rebuildEntityIndex(): void {
+    this.entityIndex.clear();
+    for (let i = 0; i < this.entities.length; i++) {
+      this.entityIndex.set(this.entities[i].id, i);
+    }
+    this.entityIndexDirty = false;
+  }
+
+  getEntity(id: number): Entity | undefined {
+    const index = this.indexOfEntity(id);
+    return index === -1 ? undefined : this.entities[index];
+  }
+
+  getNeighborCount(id: number): number {
+    const entity = this.getEntity(id);
+    if (!entity || !entity.body) return 0;
+
+    this.ensureGrid();
+    return this.queryNeighbors(entity).length;
+  }
+
+  areColliding(idA: number, idB: number): boolean {
+    if (idA === idB) return false;
+
+    const bodyA = this.getEntity(idA)?.body;
+    const bodyB = this.getEntity(idB)?.body;
+    return !!bodyA && !!bodyB && this.bodiesOverlap(bodyA, bodyB);
+  }
+
+  countInRadius(x: number, y: number, radius: number): number {
+    if (!(radius >= 0)) return 0;
+
+    this.ensureGrid();
+    return (
+      this.spatialGrid.countEntitiesInRadius(x, y, radius) +
+      this.coarseGrid.countEntitiesInRadius(x, y, radius)
+    );
+  }
+
+  countOverlaps(): number {
+    this.ensureGrid();
+    let count = 0;
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body) continue;
+
+      for (const other of this.queryNeighbors(entity)) {
+        const otherBody = other.body;
+        if (!otherBody || other.id <= entity.id) continue;
+
+        if (this.bodiesOverlap(body, otherBody)) {
+          count++;
+        }
+      }
+    }
+    return count;
+  }
+
+  getMomentum(): { x: number; y: number } {
+    let x = 0;
+    let y = 0;
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body || body.pinned) continue;
+
+      x += body.vx;
+      y += body.vy;
+    }
+    return { x, y };
+  }
+
+  getMaxSpeed(): number {
+    let maxSpeed2 = 0;
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body || this.isStatic(body)) continue;
+
+      maxSpeed2 = Math.max(maxSpeed2, body.vx * body.vx + body.vy * body.vy);
+    }
+    return Math.sqrt(maxSpeed2);
+  }
+
+  getCenterOfMass(): { x: number; y: number } | null {
+    let sumX = 0;
+    let sumY = 0;
+    let count = 0;
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body) continue;
+
+      sumX += body.x;
+      sumY += body.y;
+      count++;
+    }
+
+    return count > 0 ? { x: sumX / count, y: sumY / count } : null;
+  }
+
+  getDensestCell(): { x: number; y: number; count: number } | null {
+    let bestCol = 0;
+    let bestRow = 0;
+    let bestCount = 0;
+    this.ensureGrid();
+    this.spatialGrid.forEachActiveCell((col, row, items) => {
+      if (items.length > bestCount) {
+        bestCol = col;
+        bestRow = row;
+        bestCount = items.length;
+      }
+    });
+    if (bestCount === 0) return null;
+
+    const cellSize = this.getGridCellSize();
+    return { x: (bestCol + 0.5) * cellSize, y: (bestRow + 0.5) * cellSize, count: bestCount };
+  }
+
+  getCellContents(col: number, row: number): number[] {
+    this.ensureGrid();
+    return this.spatialGrid.getCellContents(col, row).map((entity) => entity.id);
+  }
+
+  nearestIds(x: number, y: number, k: number): number[] {
+    const count = Math.floor(k);
+    this.ensureGrid();
+    const candidates = this.spatialGrid.nearest(x, y, count);
+    candidates.push(...this.coarseGrid.nearest(x, y, count));
+    candidates.sort((a, b) => a.distance - b.distance);
+    return candidates.slice(0, count).map((hit) => hit.entity.id);
+  }
+
+  pick(x: number, y: number): number | null {
+    this.ensureGrid();
+    let bestIndex = -1;
+    for (const grid of [this.spatialGrid, this.coarseGrid]) {
+      for (const entity of grid.getEntitiesAtPoint(x, y)) {
+        const body = entity.body;
+        if (!body) continue;
+
+        const dx = body.x - x;
+        const dy = body.y - y;
+        if (dx * dx + dy * dy > body.radius * body.radius) continue;
+
+        bestIndex = Math.max(bestIndex, this.indexOfEntity(entity.id));
+      }
+    }
+
+    return bestIndex === -1 ? null : this.entities[bestIndex].id;
+  }
+
+  raycast(x: number, y: number, dx: number, dy: number, maxDistance: number): RaycastHit | null {
+    this.ensureGrid();
+    const fineHit = this.spatialGrid.raycast(x, y, dx, dy, maxDistance);
+    const coarseLimit = fineHit ? fineHit.distance : maxDistance;
+    const coarseHit = this.coarseGrid.raycast(x, y, dx, dy, coarseLimit);
+    const hit = coarseHit && (!fineHit || coarseHit.distance < fineHit.distance) ? coarseHit : fineHit;
+    return hit ? { id: hit.entity.id, distance: hit.distance } : null;
+  }
+
+  queryCapsule(x0: number, y0: number, x1: number, y1: number, radius: number): number[] {
+    this.ensureGrid();
+    const hits = this.spatialGrid.queryCapsule(x0, y0, x1, y1, radius);
+    if (this.coarseGrid.getActiveCellCount() > 0) {
+      hits.push(...this.coarseGrid.queryCapsule(x0, y0, x1, y1, radius));
+    }
+    return hits.map((entity) => entity.id);
+  }
+
+  reserveSlots(count: number): number {
+    this.lastEvictedCount = 0;
+    if (this.entityBudget <= 0) return count;
+
+    const allowed = Math.min(count, this.entityBudget);
+    const overflow = this.entities.length + allowed - this.entityBudget;
+    if (overflow > 0) {
+      this.entities.splice(0, overflow);
+      this.lastEvictedCount = overflow;
+      this.entityIndexDirty = true;
+      this.gridStale = true;
+    }
+
+    return allowed;
+  }
+
+  createEntity(body?: Body): Entity {
+    return new Entity(this.nextEntityId++, body);
+  }
+
+  setNextEntityId(id: number): void {
+    if (!Number.isSafeInteger(id) || id <= 0) {
+      throw new Error('Entity ids must be positive integers.');
+    }
+
+    let highestId = 0;
+    for (const entity of this.entities) {
+      highestId = Math.max(highestId, entity.id);
+    }
+    if (id <= highestId) {
+      throw new Error(`Next entity id must be greater than the highest live id (${highestId}).`);
+    }
+
+    this.nextEntityId = id;
+  }
+
   addEntity(entity: Entity): void {
+    this.entityIndexDirty = true;
+    this.gridStale = true;
+    this.nextEntityId = Math.max(this.nextEntityId, entity.id + 1);
+
     if (!entity.body) {
       this.entities.push(entity);
       return;
     }
 
     const radius = entity.body.radius;
+    const bounds = this.getSpawnBounds();
+    const spanX = bounds.maxX - bounds.minX;
+    const spanY = bounds.maxY - bounds.minY;
     let placed = false;
 
-    for (let attempt = 0; attempt < 100; attempt++) {
-      const x = Math.random() * this.width;
-      const y = Math.random() * this.height;
+    const retry = !this.fastSpawn && !this.placementIgnoresExisting;
+    for (let attempt = 0; attempt < 100 && retry; attempt++) {
+      const x = bounds.minX + this.rng.nextFloat() * spanX;
+      const y = bounds.minY + this.rng.nextFloat() * spanY;
 
       let collides = false;
       for (const other of this.entities) {
@@ -68,16 +649,312 @@ export class World {
     }
 
     if (!placed) {
-      entity.body.x = Math.random() * this.width;
-      entity.body.y = Math.random() * this.height;
+      const rng = this.placementIgnoresExisting ? this.placementRng : this.rng;
+      entity.body.x = bounds.minX + rng.nextFloat() * spanX;
+      entity.body.y = bounds.minY + rng.nextFloat() * spanY;
     }
 
     this.entities.push(entity);
   }
 
+  addEntityAt(entity: Entity): void {
+    this.entities.push(entity);
+    this.entityIndexDirty = true;
+    this.gridStale = true;
+    this.nextEntityId = Math.max(this.nextEntityId, entity.id + 1);
+  }
+
+  trySpawnAt(x: number, y: number, radius: number, vx: number, vy: number): number {
+    if (this.entities.length >= World.MAX_ENTITIES) return 0;
+    const bounds = this.getSpawnBounds();
+    if (
+      !(x - radius >= bounds.minX && x + radius <= bounds.maxX) ||
+      !(y - radius >= bounds.minY && y + radius <= bounds.maxY)
+    ) {
+      return 0;
+    }
+
+    this.ensureGrid();
+    if (this.spatialGrid.overlapsAny(x, y, radius) || this.coarseGrid.overlapsAny(x, y, radius)) {
+      return 0;
+    }
+    if (this.reserveSlots(1) === 0) return 0;
+
+    const gridStale = this.gridStale;
+    const entity = this.createEntity(new Body(x, y, vx, vy, radius));
+    this.addEntityAt(entity);
+    if (!gridStale) {
+      this.gridFor(radius).insert(entity);
+      this.gridStale = false;
+    }
+    return entity.id;
+  }
+
+  addEmitter(
+    x: number,
+    y: number,
+    rate: number,
+    speed: number,
+    spread: number,
+    angle: number,
+    radius: number
+  ): void {
+    this.emitters.push({ x, y, rate, speed, spread, angle, radius, accumulator: 0 });
+  }
+
+  clearEmitters(): void {
+    this.emitters = [];
+  }
+
+  addSpring(idA: number, idB: number, restLength: number, stiffness: number, damping: number): number {
+    const handle = this.nextSpringHandle++;
+    this.springs.push({ handle, idA, idB, restLength, stiffness, damping });
+    return handle;
+  }
+
+  removeSpring(handle: number): boolean {
+    const index = this.springs.findIndex((spring) => spring.handle === handle);
+    if (index === -1) return false;
+
+    this.springs.splice(index, 1);
+    return true;
+  }
+
+  resetScene(): void {
+    this.entities = [];
+    this.springs = [];
+    this.emitters = [];
+    this.entityIndexDirty = true;
+    this.frame = 0;
+    this.rng = createRng(this.rng.kind, this.seed);
+    this.placementRng = createRng(this.rng.kind, this.placementSeed(0));
+    this.spatialGrid.clear();
+    this.spatialGrid.prune();
+    this.coarseGrid.clear();
+    this.collisionEvents.clear();
+    this.overlapStreaks.clear();
+    this.contactPairs.length = 0;
+    this.movedIds.length = 0;
+    this.movementEntities.length = 0;
+    this.heatField = new Float32Array(0);
+    this.heatColumns = 0;
+    this.lastMaxDisplacement = 0;
+    this.lastMergeCount = 0;
+    this.lastFragmentCount = 0;
+    this.lastPairCheckCount = 0;
+    this.lastEvictedCount = 0;
+    this.antiStickFiredCount = 0;
+    this.nextEntityId = 1;
+  }
+
+  compact(): void {
+    this.entities = this.entities.slice();
+    this.rebuildEntityIndex();
+    this.spatialGrid.compact();
+    this.coarseGrid.compact();
+  }
+
   removeEntities(): void {
-    const halfCount = Math.floor(this.entities.length / 2);
-    this.entities = this.entities.slice(0, halfCount);
+    this.removeFraction(0.5);
+  }
+
+  removeFraction(fraction: number): void {
+    const count = this.entities.length;
+    const removeCount = Math.floor(count * Math.min(Math.max(fraction, 0), 1));
+    if (!(removeCount > 0)) return;
+
+    this.entities = this.entities.slice(0, count - removeCount);
+    this.entityIndexDirty = true;
+    this.gridStale = true;
+  }
+
+  removeEntitiesById(ids: ArrayLike<number>): number {
+    const targets = new Set<number>();
+    for (let i = 0; i < ids.length; i++) {
+      targets.add(ids[i]);
+    }
+
+    const count = this.entities.length;
+    this.entities = this.entities.filter((entity) => !targets.has(entity.id));
+    const removed = count - this.entities.length;
+    if (removed > 0) {
+      this.entityIndexDirty = true;
+      this.gridStale = true;
+    }
+    return removed;
+  }
+
+  removeRandomFraction(fraction: number): void {
+    const count = this.entities.length;
+    const removeCount = Math.floor(count * Math.min(Math.max(fraction, 0), 1));
+    if (!(removeCount > 0)) return;
+
+    const order = new Uint32Array(count);
+    for (let i = 0; i < count; i++) {
+      order[i] = i;
+    }
+
+    const removed = new Uint8Array(count);
+    for (let i = 0; i < removeCount; i++) {
+      const j = i + Math.floor(this.rng.nextFloat() * (count - i));
+      const picked = order[j];
+      order[j] = order[i];
+      order[i] = picked;
+      removed[picked] = 1;
+    }
+
+    this.entities = this.entities.filter((_, index) => removed[index] === 0);
+    this.entityIndexDirty = true;
+    this.gridStale = true;
+  }
+
+  setEntityPinned(id: number, pinned: boolean): void {
+    const body = this.getEntity(id)?.body;
+    if (!body) return;
+
+    body.pinned = pinned;
+    if (pinned) {
+      body.vx = 0;
+      body.vy = 0;
+    }
+  }
+
+  setEntityBody(id: number, x: number, y: number, vx: number, vy: number): void {
+    const body = this.getEntity(id)?.body;
+    if (!body) return;
+
+    body.x = x;
+    body.y = y;
+    body.vx = vx;
+    body.vy = vy;
+  }
+
+  setEntityDrag(id: number, drag: number | null): void {
+    if (drag !== null && (!(drag >= 0) || !Number.isFinite(drag))) {
+      throw new Error('Body drag must be a non-negative finite number.');
+    }
+
+    const body = this.getEntity(id)?.body;
+    if (body) {
+      body.drag = drag;
+    }
+  }
+
+  setEntityOneWay(id: number, nx: number, ny: number): void {
+    const body = this.getEntity(id)?.body;
+    if (!body) return;
+
+    const length = Math.sqrt(nx * nx + ny * ny);
+    if (!Number.isFinite(length)) {
+      throw new Error('One-way normal must be finite.');
+    }
+
+    body.oneWay = length > 0 ? { x: nx / length, y: ny / length } : null;
+  }
+
+  setEntityGravityScale(id: number, scale: number): void {
+    if (!Number.isFinite(scale)) {
+      throw new Error('Body gravity scale must be a finite number.');
+    }
+
+    const body = this.getEntity(id)?.body;
+    if (body) {
+      body.gravityScale = scale;
+    }
+  }
+
+  applyForce(id: number, fx: number, fy: number): void {
+    const body = this.getEntity(id)?.body;
+    if (!body || body.pinned) return;
+
+    body.ax += fx;
+    body.ay += fy;
+  }
+
+  applyForces(forces: ArrayLike<number>): void {
+    for (let i = 0; i + 2 < forces.length; i += 3) {
+      this.applyForce(forces[i], forces[i + 1], forces[i + 2]);
+    }
+  }
+
+  setEntityAspect(id: number, aspect: number): void {
+    if (!(aspect > 0) || !Number.isFinite(aspect)) {
+      throw new Error('Body aspect must be a positive finite number.');
+    }
+
+    const body = this.getEntity(id)?.body;
+    if (body) {
+      body.aspect = aspect;
+    }
+  }
+
+  readVelocities(): Float32Array {
+    const velocities = new Float32Array(this.entities.length * 2);
+    for (let i = 0; i < this.entities.length; i++) {
+      const body = this.entities[i].body;
+      if (!body) continue;
+
+      velocities[i * 2] = body.vx;
+      velocities[i * 2 + 1] = body.vy;
+    }
+    return velocities;
+  }
+
+  getVelocityArrows(scale: number): Float32Array {
+    let count = 0;
+    for (const entity of this.entities) {
+      if (entity.body) count++;
+    }
+
+    const arrows = new Float32Array(count * 4);
+    let offset = 0;
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body) continue;
+
+      arrows[offset++] = body.x;
+      arrows[offset++] = body.y;
+      arrows[offset++] = body.x + body.vx * scale;
+      arrows[offset++] = body.y + body.vy * scale;
+    }
+    return arrows;
+  }
+
+  writeVelocities(velocities: ArrayLike<number>): void {
+    const count = Math.min(this.entities.length, Math.floor(velocities.length / 2));
+    for (let i = 0; i < count; i++) {
+      const body = this.entities[i].body;
+      if (!body) continue;
+
+      body.vx = velocities[i * 2];
+      body.vy = velocities[i * 2 + 1];
+    }
+  }
+
+  randomizeVelocities(maxSpeed: number): void {
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body || body.pinned) continue;
+
+      const angle = this.rng.nextFloat() * Math.PI * 2;
+      const speed = this.rng.nextFloat() * maxSpeed;
+      body.vx = Math.cos(angle) * speed;
+      body.vy = Math.sin(angle) * speed;
+    }
+  }
+
+  scaleVelocities(factor: number): void {
+    if (!Number.isFinite(factor)) {
+      throw new Error('Velocity scale factor must be finite.');
+    }
+
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body) continue;
+
+      body.vx *= factor;
+      body.vy *= factor;
+    }
   }
 
   scaleRadii(factor: number): void {
@@ -88,45 +965,294 @@ export class World {
     }
   }
 
-  update(deltaTime: number): void {
-    this.spatialGrid.clear();
+  scaleEntityRadius(id: number, factor: number): void {
+    if (!(factor > 0) || !Number.isFinite(factor)) {
+      throw new Error('Radius scale factor must be a positive finite number.');
+    }
 
-    for (const entity of this.entities) {
-      if (!entity.body) continue;
+    const body = this.getEntity(id)?.body;
+    if (!body) return;
+
+    body.radius *= factor;
+    const bounds = this.getSpawnBounds();
+    const halfWidth = (bounds.maxX - bounds.minX) / 2;
+    const halfHeight = (bounds.maxY - bounds.minY) / 2;
+    const radius = Math.min(body.radius, halfWidth, halfHeight);
+    body.x = Math.min(Math.max(body.x, bounds.minX + radius), bounds.maxX - radius);
+    body.y = Math.min(Math.max(body.y, bounds.minY + radius), bounds.maxY - radius);
+    this.gridStale = true;
+  }
+
+  update(deltaTime: number, observer?: (phase: UpdatePhase) => void): void {
+    this.updateEmitters(deltaTime);
 
-      entity.body.x += entity.body.vx * deltaTime;
-      entity.body.y += entity.body.vy * deltaTime;
+    this.recordStartPositions();
+    this.decayHeat();
+    this.applySprings();
+    this.integrate(deltaTime);
+    observer?.('integrated');
 
-      const radius = entity.body.radius;
+    this.resolveCollisions(observer);
+    this.clampToWalls();
 
-      if (entity.body.x - radius < 0) {
-        entity.body.x = radius;
-        entity.body.vx = Math.abs(entity.body.vx);
-      } else if (entity.body.x + radius > this.width) {
-        entity.body.x = this.width - radius;
-        entity.body.vx = -Math.abs(entity.body.vx);
+    this.recordMovement();
+    this.frame++;
+    observer?.('completed');
+  }
+
+  private recordStartPositions(): void {
+    const entities = this.entities;
+    const count = entities.length;
+    if (this.movementX.length < count) {
+      const capacity = Math.max(count, this.movementX.length * 2);
+      this.movementX = new Float64Array(capacity);
+      this.movementY = new Float64Array(capacity);
+    }
+
+    const startEntities = this.movementEntities;
+    startEntities.length = count;
+    for (let i = 0; i < count; i++) {
+      const entity = entities[i];
+      startEntities[i] = entity;
+      const body = entity.body;
+      if (!body) continue;
+
+      this.movementX[i] = body.x;
+      this.movementY[i] = body.y;
+    }
+  }
+
+  private recordMovement(): void {
+    const movedIds = this.movedIds;
+    movedIds.length = 0;
+
+    const startEntities = this.movementEntities;
+    const startX = this.movementX;
+    const startY = this.movementY;
+
+    const epsilon2 = this.movementEpsilon * this.movementEpsilon;
+    for (let i = 0; i < startEntities.length; i++) {
+      const entity = startEntities[i];
+      const body = entity.body;
+      if (!body || this.getEntity(entity.id) !== entity) continue;
+
+      const dx = body.x - startX[i];
+      const dy = body.y - startY[i];
+      if (dx * dx + dy * dy > epsilon2) {
+        movedIds.push(entity.id);
       }
+    }
+  }
 
-      if (entity.body.y - radius < 0) {
-        entity.body.y = radius;
-        entity.body.vy = Math.abs(entity.body.vy);
-      } else if (entity.body.y + radius > this.height) {
-        entity.body.y = this.height - radius;
-        entity.body.vy = -Math.abs(entity.body.vy);
+  private decayHeat(): void {
+    const cellSize = this.getGridCellSize();
+    const columns = Math.ceil(this.width / cellSize);
+    const rows = Math.ceil(this.height / cellSize);
+    if (this.heatColumns !== columns || this.heatField.length !== columns * rows) {
+      this.heatField = new Float32Array(columns * rows);
+      this.heatColumns = columns;
+      return;
+    }
+
+    const heat = this.heatField;
+    const decay = this.heatDecay;
+    for (let i = 0; i < heat.length; i++) {
+      heat[i] *= decay;
+    }
+  }
+
+  private addHeat(x: number, y: number, energy: number): void {
+    const columns = this.heatColumns;
+    if (columns === 0) return;
+
+    const cellSize = this.getGridCellSize();
+    const col = Math.floor(x / cellSize);
+    const row = Math.floor(y / cellSize);
+    const index = row * columns + col;
+    if (col < 0 || col >= columns || index < 0 || index >= this.heatField.length) return;
+
+    this.heatField[index] += energy;
+  }
+
+  private inFrozenRegion(body: Body): boolean {
+    for (const region of this.frozenRegions) {
+      if (
+        body.x >= region.minX &&
+        body.x <= region.maxX &&
+        body.y >= region.minY &&
+        body.y <= region.maxY
+      ) {
+        return true;
+      }
+    }
+    return false;
+  }
+
+  private isStatic(body: Body): boolean {
+    return body.pinned || (this.frozenBodies.size > 0 && this.frozenBodies.has(body));
+  }
+
+  private gridFor(radius: number): SpatialGrid {
+    const large = this.twoLevelGrid && radius * 2 > this.getGridCellSize();
+    return large ? this.coarseGrid : this.spatialGrid;
+  }
+
+  private usesBruteForce(): boolean {
+    return (
+      this.broadphase === 'brute_force' ||
+      (this.broadphase === 'auto' && this.entities.length < this.bruteForceThreshold)
+    );
+  }
+
+  private ensureGrid(): void {
+    if (!this.gridStale) return;
+
+    this.spatialGrid.clear();
+    this.coarseGrid.clear();
+    for (const entity of this.entities) {
+      if (entity.body) {
+        this.gridFor(entity.body.radius).insert(entity);
       }
+    }
+    this.spatialGrid.prune();
+    this.gridStale = false;
+  }
+
+  private queryNeighbors(entity: Entity): Entity[] {
+    const neighbors = this.spatialGrid.query(entity);
+    if (this.coarseGrid.getActiveCellCount() === 0) return neighbors;
+
+    return neighbors.concat(this.coarseGrid.query(entity));
+  }
+
+  private integrate(deltaTime: number): void {
+    const useGrid = !this.usesBruteForce();
+    this.gridStale = !useGrid;
+    this.spatialGrid.clear();
+    this.coarseGrid.clear();
+
+    const entities = this.entities;
+    const kick = this.brownianStrength * deltaTime;
+    const frozen = this.frozenBodies;
+    frozen.clear();
+    let maxDisplacement2 = 0;
+    let kept = 0;
+    for (let i = 0; i < entities.length; i++) {
+      const entity = entities[i];
+      const body = entity.body;
+      if (body) {
+        if (this.frozenRegions.length > 0 && this.inFrozenRegion(body)) {
+          frozen.add(body);
+        } else if (!body.pinned) {
+          body.vx += (body.ax + this.gravityX * body.gravityScale) * deltaTime;
+          body.vy += (body.ay + this.gravityY * body.gravityScale) * deltaTime;
+
+          if (kick > 0) {
+            body.vx += this.rng.nextRange(-kick, kick);
+            body.vy += this.rng.nextRange(-kick, kick);
+          }
+
+          if (this.pointerGravity) {
+            const pointer = this.pointerGravity;
+            const dx = pointer.x - body.x;
+            const dy = pointer.y - body.y;
+            const scale = (pointer.strength * deltaTime) / Math.sqrt(dx * dx + dy * dy + 64);
+            body.vx += dx * scale;
+            body.vy += dy * scale;
+          }
+
+          if (this.flowField) {
+            this.applyFlow(body, this.flowField, deltaTime);
+          }
 
-      this.spatialGrid.insert(entity);
+          const drag = body.drag ?? 0;
+          if (drag > 0 || this.damping > 0) {
+            const factor =
+              Math.max(1 - drag * deltaTime, 0) * Math.max(1 - this.damping * deltaTime, 0);
+            body.vx *= factor;
+            body.vy *= factor;
+          }
+
+          const stepX = body.vx * deltaTime;
+          const stepY = body.vy * deltaTime;
+          body.x += stepX;
+          body.y += stepY;
+          body.angle += body.angularVelocity * deltaTime;
+          maxDisplacement2 = Math.max(maxDisplacement2, stepX * stepX + stepY * stepY);
+
+          if (!this.applyEdges(body)) continue;
+        }
+        body.ax = 0;
+        body.ay = 0;
+
+        if (useGrid) {
+          this.gridFor(body.radius).insert(entity);
+        }
+      }
+      entities[kept++] = entity;
+    }
+    if (kept !== entities.length) {
+      entities.length = kept;
+      this.entityIndexDirty = true;
     }
+    this.lastMaxDisplacement = Math.sqrt(maxDisplacement2);
+    this.spatialGrid.prune();
+  }
+
+  private applyFlow(body: Body, field: FlowField, deltaTime: number): void {
+    const fx = Math.min(Math.max((body.x / this.width) * field.cols - 0.5, 0), field.cols - 1);
+    const fy = Math.min(Math.max((body.y / this.height) * field.rows - 0.5, 0), field.rows - 1);
+    const col0 = Math.floor(fx);
+    const row0 = Math.floor(fy);
+    const col1 = Math.min(col0 + 1, field.cols - 1);
+    const row1 = Math.min(row0 + 1, field.rows - 1);
+    const tx = fx - col0;
+    const ty = fy - row0;
 
+    const vectors = field.vectors;
+    const sample = (offset: 0 | 1): number => {
+      const top =
+        vectors[(row0 * field.cols + col0) * 2 + offset] * (1 - tx) +
+        vectors[(row0 * field.cols + col1) * 2 + offset] * tx;
+      const bottom =
+        vectors[(row1 * field.cols + col0) * 2 + offset] * (1 - tx) +
+        vectors[(row1 * field.cols + col1) * 2 + offset] * tx;
+      return top * (1 - ty) + bottom * ty;
+    };
+
+    const blend = Math.min(field.strength * deltaTime, 1);
+    body.vx += (sample(0) - body.vx) * blend;
+    body.vy += (sample(1) - body.vy) * blend;
+  }
+
+  private resolveCollisions(observer?: (phase: UpdatePhase) => void): void {
     const checkedPairs = new Set<bigint>();
+    const streaks = this.antiStick ? new Map<bigint, number>() : null;
+    const merging = this.mergeOnCollision ? new Set<Entity>() : null;
+    const merges: Entity[] = [];
+    const fragments = Number.isFinite(this.fragmentSizeThreshold) ? new Map<Entity, number>() : null;
+    const contacts = this.contactPairs;
+    contacts.length = 0;
+    const repeatPairs: Body[] | null = this.contactPasses > 1 ? [] : null;
+    this.antiStickFiredCount = 0;
+    this.lastPairCheckCount = 0;
 
     for (const entityA of this.entities) {
       if (!entityA.body) continue;
 
-      const nearby = this.spatialGrid.query(entityA);
+      const large = this.gridFor(entityA.body.radius) === this.coarseGrid;
+      const nearby = this.gridStale
+        ? this.entities
+        : large
+          ? this.coarseGrid.query(entityA)
+          : this.queryNeighbors(entityA);
+      const neighborLimit = this.maxNeighborsPerBody > 0 ? this.maxNeighborsPerBody : Infinity;
+      const start = nearby.length > neighborLimit ? (this.frame * neighborLimit) % nearby.length : 0;
+      let neighborChecks = 0;
 
-      for (const entityB of nearby) {
-        if (!entityB.body) continue;
+      for (let n = 0; n < nearby.length; n++) {
+        const entityB = nearby[(start + n) % nearby.length];
+        if (!entityB.body || entityB === entityA) continue;
 
         const idA = entityA.id;
         const idB = entityB.id;
@@ -135,65 +1261,676 @@ export class World {
         const pairKey = (BigInt(minId) << 32n) | BigInt(maxId);
 
         if (checkedPairs.has(pairKey)) continue;
+        if (neighborChecks++ >= neighborLimit) break;
         checkedPairs.add(pairKey);
+        this.lastPairCheckCount++;
 
-        const bodyA = entityA.body;
-        const bodyB = entityB.body;
-        const dx = bodyB.x - bodyA.x;
-        const dy = bodyB.y - bodyA.y;
-        const d2 = dx * dx + dy * dy;
-        const minDist = bodyA.radius + bodyB.radius;
-        const minDist2 = minDist * minDist;
+        if (
+          merging &&
+          !this.isStatic(entityA.body) &&
+          !this.isStatic(entityB.body) &&
+          this.bodiesOverlap(entityA.body, entityB.body)
+        ) {
+          contacts.push(idA, idB);
+          if (!merging.has(entityA) && !merging.has(entityB)) {
+            merging.add(entityA);
+            merging.add(entityB);
+            merges.push(entityA, entityB);
+          }
+          continue;
+        }
 
-        if (d2 < minDist2 && d2 > 0) {
-          const distance = Math.sqrt(d2);
-          const nx = dx / distance;
-          const ny = dy / distance;
+        if (this.passesOneWay(entityA.body, entityB.body)) continue;
 
-          const dvx = bodyA.vx - bodyB.vx;
-          const dvy = bodyA.vy - bodyB.vy;
+        if (fragments) {
+          this.queueFragments(entityA, entityB, fragments);
+        }
 
-          const vn = dvx * nx + dvy * ny;
+        const checkMomentum =
+          this.debugChecks &&
+          this.collisionModel === 'impulse_elastic' &&
+          this.correctionDamping === 0 &&
+          !this.isStatic(entityA.body) &&
+          !this.isStatic(entityB.body);
+        const momentumX = entityA.body.vx + entityB.body.vx;
+        const momentumY = entityA.body.vy + entityB.body.vy;
 
-          if (vn > 0) {
-            const impulse = vn;
+        this.recordImpact(entityA, entityB);
+        const overlapping = this.resolvePair(entityA.body, entityB.body);
+        repeatPairs?.push(entityA.body, entityB.body);
+        if (checkMomentum) {
+          this.assertMomentum(entityA, entityB, momentumX, momentumY);
+        }
+        if (overlapping) {
+          contacts.push(idA, idB);
+        }
+        if (streaks && overlapping) {
+          const streak = (this.overlapStreaks.get(pairKey) ?? 0) + 1;
+          if (streak > this.antiStickFrames) {
+            this.breakOverlap(entityA.body, entityB.body);
+            this.antiStickFiredCount++;
+          } else {
+            streaks.set(pairKey, streak);
+          }
+        }
+      }
+    }
 
-            bodyA.vx -= impulse * nx;
-            bodyA.vy -= impulse * ny;
-            bodyB.vx += impulse * nx;
-            bodyB.vy += impulse * ny;
+    observer?.('solver_pass');
+    if (repeatPairs) {
+      const order = this.orderPairsAlongNormal(repeatPairs);
+      for (let pass = 1; pass < this.contactPasses; pass++) {
+        const ascending = pass % 2 === 1;
+        for (let i = 0; i < order.length; i++) {
+          const pair = order[ascending ? i : order.length - 1 - i] * 2;
+          this.resolveRepeatPair(repeatPairs[pair], repeatPairs[pair + 1]);
+        }
+        observer?.('solver_pass');
+      }
+    }
 
-            const overlap = minDist - distance;
-            const separationX = nx * overlap * 0.5;
-            const separationY = ny * overlap * 0.5;
+    if (streaks) {
+      this.overlapStreaks = streaks;
+    } else if (this.overlapStreaks.size > 0) {
+      this.overlapStreaks.clear();
+    }
 
-            bodyA.x -= separationX;
-            bodyA.y -= separationY;
-            bodyB.x += separationX;
-            bodyB.y += separationY;
-          }
+    this.applyMerges(merges);
+    this.applyFragments(fragments);
+  }
+
+  private recordImpact(entityA: Entity, entityB: Entity): void {
+    const bodyA = entityA.body;
+    const bodyB = entityB.body;
+    if (!bodyA || !bodyB || (this.isStatic(bodyA) && this.isStatic(bodyB))) return;
+
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const d2 = dx * dx + dy * dy;
+    const minDist = bodyA.radius + bodyB.radius;
+    if (d2 <= 0 || d2 >= minDist * minDist) return;
+
+    const distance = Math.sqrt(d2);
+    const nx = dx / distance;
+    const ny = dy / distance;
+    const speed = (bodyA.vx - bodyB.vx) * nx + (bodyA.vy - bodyB.vy) * ny;
+    if (speed <= 0) return;
+
+    this.collisionEvents.push(
+      entityA.id,
+      entityB.id,
+      bodyA.x + nx * bodyA.radius,
+      bodyA.y + ny * bodyA.radius,
+      speed
+    );
+  }
+
+  private passesOneWay(bodyA: Body, bodyB: Body): boolean {
+    const platform = bodyA.pinned && bodyA.oneWay ? bodyA : bodyB;
+    if (!platform.pinned || !platform.oneWay) return false;
+
+    const other = platform === bodyA ? bodyB : bodyA;
+    const normal = platform.oneWay;
+    const side = (other.x - platform.x) * normal.x + (other.y - platform.y) * normal.y;
+    const approach = (other.vx - platform.vx) * normal.x + (other.vy - platform.vy) * normal.y;
+    return side < 0 || approach > 0;
+  }
+
+  private assertMomentum(entityA: Entity, entityB: Entity, momentumX: number, momentumY: number): void {
+    const bodyA = entityA.body;
+    const bodyB = entityB.body;
+    if (!bodyA || !bodyB) return;
+
+    const driftX = bodyA.vx + bodyB.vx - momentumX;
+    const driftY = bodyA.vy + bodyB.vy - momentumY;
+    const tolerance = 1e-6 * Math.max(1, Math.abs(momentumX) + Math.abs(momentumY));
+    if (Math.abs(driftX) > tolerance || Math.abs(driftY) > tolerance) {
+      throw new Error(
+        `Momentum not conserved: drift (${driftX}, ${driftY}). ` +
+          this.formatDebugContext(this.debugContext([entityA.id, entityB.id]))
+      );
+    }
+  }
+
+  debugContext(entityIds: number[]): DebugContext {
+    return { frame: this.frame, seed: this.seed, entityIds };
+  }
+
+  private formatDebugContext(context: DebugContext): string {
+    return (
+      `[frame ${context.frame}, seed ${context.seed}, rng ${this.rng.kind}, ` +
+      `entities ${context.entityIds.join(', ')}]`
+    );
+  }
+
+  private queueFragments(entityA: Entity, entityB: Entity, fragments: Map<Entity, number>): void {
+    const bodyA = entityA.body;
+    const bodyB = entityB.body;
+    if (!bodyA || !bodyB || !this.bodiesOverlap(bodyA, bodyB)) return;
+
+    const threshold = this.fragmentSizeThreshold;
+    if (bodyA.radius <= threshold && bodyB.radius <= threshold) return;
+
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const distance = Math.sqrt(dx * dx + dy * dy);
+    if (distance === 0) return;
+
+    const impact = ((bodyA.vx - bodyB.vx) * dx + (bodyA.vy - bodyB.vy) * dy) / distance;
+    if (impact <= this.fragmentSpeedThreshold) return;
+
+    for (const entity of [entityA, entityB]) {
+      const body = entity.body;
+      if (body && !this.isStatic(body) && body.radius > threshold) {
+        fragments.set(entity, Math.max(fragments.get(entity) ?? 0, impact));
+      }
+    }
+  }
+
+  private applyFragments(fragments: Map<Entity, number> | null): void {
+    this.lastFragmentCount = 0;
+    if (!fragments) return;
+
+    let evicted = 0;
+    for (const [entity, impact] of fragments) {
+      if (this.entities.length >= World.MAX_ENTITIES) break;
+      if (this.reserveSlots(1) === 0) break;
+      evicted += this.lastEvictedCount;
+
+      const body = entity.body;
+      if (!body || (evicted > 0 && this.getEntity(entity.id) !== entity)) continue;
+
+      const radius = body.radius / Math.SQRT2;
+      const angle = this.rng.nextFloat() * Math.PI * 2;
+      const axisX = Math.cos(angle);
+      const axisY = Math.sin(angle);
+      const kick = impact * 0.5;
+
+      const fragment = body.clone();
+      fragment.radius = radius;
+      fragment.x = body.x + axisX * radius;
+      fragment.y = body.y + axisY * radius;
+      fragment.vx = body.vx + axisX * kick;
+      fragment.vy = body.vy + axisY * kick;
+
+      body.radius = radius;
+      body.x -= axisX * radius;
+      body.y -= axisY * radius;
+      body.vx -= axisX * kick;
+      body.vy -= axisY * kick;
+
+      this.addEntityAt(this.createEntity(fragment));
+      this.lastFragmentCount++;
+    }
+    if (fragments.size > 0) {
+      this.lastEvictedCount = evicted;
+    }
+  }
+
+  private applyMerges(pairs: readonly Entity[]): void {
+    this.lastMergeCount = 0;
+    if (pairs.length === 0) return;
+
+    const absorbed = new Set<Entity>();
+    for (let i = 0; i < pairs.length; i += 2) {
+      const first = pairs[i];
+      const second = pairs[i + 1];
+      const firstBody = first.body;
+      const secondBody = second.body;
+      if (!firstBody || !secondBody) continue;
+
+      const firstSurvives = firstBody.radius >= secondBody.radius;
+      const a = firstSurvives ? firstBody : secondBody;
+      const b = firstSurvives ? secondBody : firstBody;
+
+      const massA = a.radius * a.radius;
+      const massB = b.radius * b.radius;
+      const totalMass = massA + massB;
+      if (totalMass <= 0) continue;
+
+      a.x = (a.x * massA + b.x * massB) / totalMass;
+      a.y = (a.y * massA + b.y * massB) / totalMass;
+      a.vx = (a.vx * massA + b.vx * massB) / totalMass;
+      a.vy = (a.vy * massA + b.vy * massB) / totalMass;
+      a.radius = Math.sqrt(totalMass);
+      absorbed.add(firstSurvives ? second : first);
+    }
+
+    if (absorbed.size === 0) return;
+
+    this.entities = this.entities.filter((entity) => !absorbed.has(entity));
+    this.entityIndexDirty = true;
+    this.gridStale = true;
+    this.lastMergeCount = absorbed.size;
+  }
+
+  private bodiesOverlap(bodyA: Body, bodyB: Body): boolean {
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const minDist = bodyA.radius + bodyB.radius;
+    return dx * dx + dy * dy < minDist * minDist;
+  }
+
+  private breakOverlap(bodyA: Body, bodyB: Body): void {
+    const invMassA = this.isStatic(bodyA) ? 0 : 1;
+    const invMassB = this.isStatic(bodyB) ? 0 : 1;
+    const invMassSum = invMassA + invMassB;
+    if (invMassSum === 0) return;
+
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const distance = Math.sqrt(dx * dx + dy * dy);
+    const baseAngle = distance > 0 ? Math.atan2(dy, dx) : this.rng.nextFloat() * Math.PI * 2;
+    const angle = baseAngle + this.rng.nextRange(-0.5, 0.5);
+    const nx = Math.cos(angle);
+    const ny = Math.sin(angle);
+
+    const minDist = bodyA.radius + bodyB.radius;
+    const correction = Math.max(minDist - distance, 0) / invMassSum;
+    const kick = (minDist * this.rng.nextRange(1, 2)) / invMassSum;
+
+    bodyA.x -= nx * correction * invMassA;
+    bodyA.y -= ny * correction * invMassA;
+    bodyB.x += nx * correction * invMassB;
+    bodyB.y += ny * correction * invMassB;
+
+    bodyA.vx -= nx * kick * invMassA;
+    bodyA.vy -= ny * kick * invMassA;
+    bodyB.vx += nx * kick * invMassB;
+    bodyB.vy += ny * kick * invMassB;
+  }
+
+  private applySprings(): void {
+    for (const spring of this.springs) {
+      const bodyA = this.getEntity(spring.idA)?.body;
+      const bodyB = this.getEntity(spring.idB)?.body;
+      if (!bodyA || !bodyB) continue;
+
+      const dx = bodyB.x - bodyA.x;
+      const dy = bodyB.y - bodyA.y;
+      const distance = Math.sqrt(dx * dx + dy * dy);
+      if (distance === 0) continue;
+
+      const nx = dx / distance;
+      const ny = dy / distance;
+      const relativeSpeed = (bodyB.vx - bodyA.vx) * nx + (bodyB.vy - bodyA.vy) * ny;
+      const force = spring.stiffness * (distance - spring.restLength) + spring.damping * relativeSpeed;
+      const forceX = force * nx;
+      const forceY = force * ny;
+
+      if (!bodyA.pinned) {
+        bodyA.ax += forceX;
+        bodyA.ay += forceY;
+      }
+      if (!bodyB.pinned) {
+        bodyB.ax -= forceX;
+        bodyB.ay -= forceY;
+      }
+    }
+  }
+
+  private applyEdges(body: Body): boolean {
+    const radius = body.radius;
+    const edges = this.edgeBehaviors;
+    const inset = this.boundsInset;
+    const maxX = this.width - inset;
+    const maxY = this.height - inset;
+
+    if (body.x - radius < inset) {
+      if (!this.resolveEdge(edges.left, body, 'x', inset, maxX, -1)) return false;
+    } else if (body.x + radius > maxX) {
+      if (!this.resolveEdge(edges.right, body, 'x', inset, maxX, 1)) return false;
+    }
+
+    if (body.y - radius < inset) {
+      if (!this.resolveEdge(edges.top, body, 'y', inset, maxY, -1)) return false;
+    } else if (body.y + radius > maxY) {
+      if (!this.resolveEdge(edges.bottom, body, 'y', inset, maxY, 1)) return false;
+    }
+
+    return true;
+  }
+
+  private clampToWalls(): void {
+    const edges = this.edgeBehaviors;
+    const solid = (edge: Edge): boolean =>
+      edges[edge] === 'bounce' || edges[edge] === 'absorb';
+    const left = solid('left');
+    const right = solid('right');
+    const top = solid('top');
+    const bottom = solid('bottom');
+    if (!left && !right && !top && !bottom) return;
+
+    const inset = this.boundsInset;
+    const maxX = this.width - inset;
+    const maxY = this.height - inset;
+    let clamped = false;
+
+    for (const entity of this.entities) {
+      const body = entity.body;
+      if (!body || this.isStatic(body)) continue;
+      const radius = body.radius;
+
+      if (left && body.x - radius < inset) {
+        body.x = inset + radius;
+        clamped = true;
+      } else if (right && body.x + radius > maxX) {
+        body.x = maxX - radius;
+        clamped = true;
+      }
+
+      if (top && body.y - radius < inset) {
+        body.y = inset + radius;
+        clamped = true;
+      } else if (bottom && body.y + radius > maxY) {
+        body.y = maxY - radius;
+        clamped = true;
+      }
+    }
+
+    if (clamped) {
+      this.gridStale = true;
+    }
+  }
+
+  private resolveEdge(
+    behavior: EdgeBehavior,
+    body: Body,
+    axis: 'x' | 'y',
+    min: number,
+    max: number,
+    side: -1 | 1
+  ): boolean {
+    const velocity = axis === 'x' ? 'vx' : 'vy';
+    const radius = body.radius;
+
+    switch (behavior) {
+      case 'bounce': {
+        const speed = Math.abs(body[velocity]) * this.wallRestitution;
+        if (side < 0) {
+          body[axis] = min + radius;
+          body[velocity] = speed;
+        } else {
+          body[axis] = max - radius;
+          body[velocity] = -speed;
+        }
+        return true;
+      }
+      case 'wrap': {
+        if (side < 0 && body[axis] < min) {
+          body[axis] += max - min;
+        } else if (side > 0 && body[axis] > max) {
+          body[axis] -= max - min;
         }
+        return true;
+      }
+      case 'kill': {
+        return false;
+      }
+      case 'absorb': {
+        body[axis] = side < 0 ? min + radius : max - radius;
+        body.vx = 0;
+        body.vy = 0;
+        return true;
+      }
+      case 'open': {
+        return side < 0 ? body[axis] + radius >= min : body[axis] - radius <= max;
+      }
+      default: {
+        const exhaustiveCheck: never = behavior;
+        throw new Error(`Unhandled EdgeBehavior: ${JSON.stringify(exhaustiveCheck)}`);
+      }
+    }
+  }
+
+  private resolvePair(bodyA: Body, bodyB: Body): boolean {
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const d2 = dx * dx + dy * dy;
+    const minDist = bodyA.radius + bodyB.radius;
+    const minDist2 = minDist * minDist;
+
+    if (d2 >= minDist2) return false;
+    if (d2 <= 0) return true;
+
+    const invMassA = this.isStatic(bodyA) ? 0 : 1;
+    const invMassB = this.isStatic(bodyB) ? 0 : 1;
+    const invMassSum = invMassA + invMassB;
+    if (invMassSum === 0) return true;
+
+    const distance = Math.sqrt(d2);
+    const nx = dx / distance;
+    const ny = dy / distance;
+
+    if (
+      this.collisionModel !== 'positional_only' &&
+      !this.applyContactImpulse(bodyA, bodyB, nx, ny, invMassA, invMassB)
+    ) {
+      return true;
+    }
+
+    const penetration = Math.max(minDist - distance - this.correctionSlop, 0);
+    const correction = (penetration * this.correctionPercent) / invMassSum;
+    const separationX = nx * correction;
+    const separationY = ny * correction;
+
+    bodyA.x -= separationX * invMassA;
+    bodyA.y -= separationY * invMassA;
+    bodyB.x += separationX * invMassB;
+    bodyB.y += separationY * invMassB;
+
+    if (this.correctionDamping > 0 && correction > 0) {
+      this.dampCorrectedVelocity(bodyA, correction * invMassA);
+      this.dampCorrectedVelocity(bodyB, correction * invMassB);
+    }
+    return true;
+  }
+
+  private resolveRepeatPair(bodyA: Body, bodyB: Body): void {
+    if (this.resolvePair(bodyA, bodyB) || this.collisionModel === 'positional_only') return;
+
+    const dx = bodyB.x - bodyA.x;
+    const dy = bodyB.y - bodyA.y;
+    const d2 = dx * dx + dy * dy;
+    const reach = bodyA.radius + bodyB.radius + this.correctionSlop + World.CONTACT_TOLERANCE;
+    if (d2 >= reach * reach) return;
+
+    const invMassA = this.isStatic(bodyA) ? 0 : 1;
+    const invMassB = this.isStatic(bodyB) ? 0 : 1;
+    if (invMassA + invMassB === 0) return;
+
+    const distance = Math.sqrt(d2);
+    this.applyContactImpulse(bodyA, bodyB, dx / distance, dy / distance, invMassA, invMassB);
+  }
+
+  private orderPairsAlongNormal(pairs: readonly Body[]): Uint32Array {
+    let axisX = 0;
+    let axisY = 0;
+    for (let i = 0; i < pairs.length; i += 2) {
+      const dx = pairs[i + 1].x - pairs[i].x;
+      const dy = pairs[i + 1].y - pairs[i].y;
+      const length = Math.sqrt(dx * dx + dy * dy);
+      if (length === 0) continue;
+
+      const sign = dx > 0 || (dx === 0 && dy > 0) ? 1 : -1;
+      axisX += (dx / length) * sign;
+      axisY += (dy / length) * sign;
+    }
+    if (axisX === 0 && axisY === 0) {
+      axisX = 1;
+    }
+
+    const count = pairs.length / 2;
+    const keys = new Float64Array(count);
+    const order = new Uint32Array(count);
+    for (let pair = 0; pair < count; pair++) {
+      const bodyA = pairs[pair * 2];
+      const bodyB = pairs[pair * 2 + 1];
+      keys[pair] = (bodyA.x + bodyB.x) * axisX + (bodyA.y + bodyB.y) * axisY;
+      order[pair] = pair;
+    }
+    return order.sort((a, b) => keys[a] - keys[b]);
+  }
+
+  private applyContactImpulse(
+    bodyA: Body,
+    bodyB: Body,
+    nx: number,
+    ny: number,
+    invMassA: number,
+    invMassB: number
+  ): boolean {
+    const vn = (bodyA.vx - bodyB.vx) * nx + (bodyA.vy - bodyB.vy) * ny;
+    if (vn <= 0) return false;
+
+    const invMassSum = invMassA + invMassB;
+    const restitution = this.collisionModel === 'inelastic' ? 0 : this.bodyRestitution;
+    const impulse = ((1 + restitution) * vn) / invMassSum;
+    if (restitution < 1) {
+      this.addHeat(
+        bodyA.x + nx * bodyA.radius,
+        bodyA.y + ny * bodyA.radius,
+        (0.5 * (1 - restitution * restitution) * vn * vn) / invMassSum
+      );
+    }
+
+    bodyA.vx -= impulse * invMassA * nx;
+    bodyA.vy -= impulse * invMassA * ny;
+    bodyB.vx += impulse * invMassB * nx;
+    bodyB.vy += impulse * invMassB * ny;
+
+    if (this.rotationalCollisions) {
+      this.applySpinImpulse(bodyA, bodyB, nx, ny, invMassA, invMassB, impulse);
+    }
+    return true;
+  }
+
+  private applySpinImpulse(
+    bodyA: Body,
+    bodyB: Body,
+    nx: number,
+    ny: number,
+    invMassA: number,
+    invMassB: number,
+    normalImpulse: number
+  ): void {
+    const tx = -ny;
+    const ty = nx;
+    const radiusA = bodyA.radius;
+    const radiusB = bodyB.radius;
+    if (radiusA <= 0 || radiusB <= 0) return;
+
+    const surfaceSpeedA = bodyA.vx * tx + bodyA.vy * ty + bodyA.angularVelocity * radiusA;
+    const surfaceSpeedB = bodyB.vx * tx + bodyB.vy * ty - bodyB.angularVelocity * radiusB;
+    const slip = surfaceSpeedA - surfaceSpeedB;
+
+    const effectiveInvMass = 3 * (invMassA + invMassB);
+    if (effectiveInvMass === 0) return;
+
+    const limit = this.contactFriction * Math.abs(normalImpulse);
+    const tangentImpulse = Math.min(Math.max(-slip / effectiveInvMass, -limit), limit);
+
+    bodyA.vx += tangentImpulse * invMassA * tx;
+    bodyA.vy += tangentImpulse * invMassA * ty;
+    bodyB.vx -= tangentImpulse * invMassB * tx;
+    bodyB.vy -= tangentImpulse * invMassB * ty;
+    bodyA.angularVelocity += (2 * tangentImpulse * invMassA) / radiusA;
+    bodyB.angularVelocity += (2 * tangentImpulse * invMassB) / radiusB;
+  }
+
+  private dampCorrectedVelocity(body: Body, shift: number): void {
+    if (shift <= 0 || body.radius <= 0) return;
+
+    const factor = Math.max(1 - (this.correctionDamping * shift) / body.radius, 0);
+    body.vx *= factor;
+    body.vy *= factor;
+  }
+
+  private updateEmitters(deltaTime: number): void {
+    for (const emitter of this.emitters) {
+      emitter.accumulator += emitter.rate * deltaTime;
+      if (emitter.accumulator < 1) continue;
+
+      const count = Math.floor(emitter.accumulator);
+      emitter.accumulator -= count;
+
+      const available = Math.max(World.MAX_ENTITIES - this.entities.length, 0);
+      const spawnCount = this.reserveSlots(Math.min(count, available));
+      for (let i = 0; i < spawnCount; i++) {
+        const angle = emitter.angle + (this.rng.nextFloat() - 0.5) * emitter.spread;
+        const dirX = Math.cos(angle);
+        const dirY = Math.sin(angle);
+        const offset = (i * emitter.speed * deltaTime) / spawnCount;
+        this.addEntityAt(
+          this.createEntity(
+            new Body(
+              emitter.x + dirX * offset,
+              emitter.y + dirY * offset,
+              dirX * emitter.speed,
+              dirY * emitter.speed,
+              emitter.radius
+            )
+          )
+        );
       }
     }
   }
 
   clone(): World {
-    const cloned = new World();
-    cloned.width = this.width;
-    cloned.height = this.height;
+    const cloned = new World(this.seed, this.rng.kind);
+    cloned.rng = this.rng.clone();
+    cloned.placementRng = this.placementRng.clone();
+    cloned.frame = this.frame;
+    cloned.resize(this.width, this.height);
+    cloned.setGridMode(this.getGridMode());
     cloned.setGridCellSize(this.getGridCellSize());
+    cloned.twoLevelGrid = this.twoLevelGrid;
+    cloned.broadphase = this.broadphase;
+    cloned.bruteForceThreshold = this.bruteForceThreshold;
+    cloned.emitters = this.emitters.map((emitter) => ({ ...emitter }));
+    cloned.springs = this.springs.map((spring) => ({ ...spring }));
+    cloned.nextSpringHandle = this.nextSpringHandle;
+    cloned.nextEntityId = this.nextEntityId;
+    cloned.entityBudget = this.entityBudget;
+    cloned.fastSpawn = this.fastSpawn;
+    cloned.placementIgnoresExisting = this.placementIgnoresExisting;
+    cloned.damping = this.damping;
+    cloned.gravityX = this.gravityX;
+    cloned.gravityY = this.gravityY;
+    cloned.brownianStrength = this.brownianStrength;
+    cloned.pointerGravity = this.pointerGravity ? { ...this.pointerGravity } : null;
+    cloned.frozenRegions = this.frozenRegions.map((region) => ({ ...region }));
+    cloned.flowField = this.flowField
+      ? { ...this.flowField, vectors: this.flowField.vectors.slice() }
+      : null;
+    cloned.debugChecks = this.debugChecks;
+    cloned.antiStick = this.antiStick;
+    cloned.antiStickFrames = this.antiStickFrames;
+    cloned.mergeOnCollision = this.mergeOnCollision;
+    cloned.fragmentSizeThreshold = this.fragmentSizeThreshold;
+    cloned.fragmentSpeedThreshold = this.fragmentSpeedThreshold;
+    cloned.heatField = this.heatField.slice();
+    cloned.heatDecay = this.heatDecay;
+    cloned.heatColumns = this.heatColumns;
+    cloned.collisionEvents.setCapacity(this.collisionEvents.getCapacity());
+    cloned.collisionModel = this.collisionModel;
+    cloned.correctionSlop = this.correctionSlop;
+    cloned.correctionPercent = this.correctionPercent;
+    cloned.correctionDamping = this.correctionDamping;
+    cloned.maxNeighborsPerBody = this.maxNeighborsPerBody;
+    cloned.contactPasses = this.contactPasses;
+    cloned.rotationalCollisions = this.rotationalCollisions;
+    cloned.contactFriction = this.contactFriction;
+    cloned.wallRestitution = this.wallRestitution;
+    cloned.bodyRestitution = this.bodyRestitution;
+    cloned.edgeBehaviors = { ...this.edgeBehaviors };
+    cloned.boundsInset = this.boundsInset;
 
     for (const entity of this.entities) {
-      const clonedEntity = new Entity(undefined, undefined, undefined, entity.id);
+      const clonedEntity = new Entity(entity.id);
 
       if (entity.body) {
-        clonedEntity.body = new Body(
-          entity.body.x,
-          entity.body.y,
-          entity.body.vx,
-          entity.body.vy,
-          entity.body.radius
-        );
+        clonedEntity.body = entity.body.clone();
       }
 
       if (entity.hp) {
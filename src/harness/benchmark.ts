@@ -0,0 +1,105 @@
+import { Broadphase, GameSimulation, GridMode } from '../GameSimulation/GameSimulation.js';
+
+export interface BenchmarkResult {
+  bodies: number;
+  steps: number;
+  totalMs: number;
+  msPerStep: number;
+}
+
+export interface BroadphaseComparison {
+  bodies: number;
+  gridMsPerStep: number;
+  bruteForceMsPerStep: number;
+}
+
+export interface GridModeComparison {
+  bodies: number;
+  rebuildMsPerStep: number;
+  incrementalMsPerStep: number;
+}
+
+export function runBenchmark(seed = 1, bodyCount = 10000, steps = 600): BenchmarkResult {
+  const simulation = new GameSimulation({ seed });
+  simulation.next_tick([{ type: 'spawn_poisson', count: bodyCount, radius: 4, speed: 64 }], 0);
+  return measure(simulation, steps);
+}
+
+export function runMixedSizeBenchmark(
+  twoLevelGrid: boolean,
+  seed = 1,
+  steps = 600
+): BenchmarkResult {
+  const simulation = new GameSimulation({ seed });
+  simulation.next_tick(
+    [
+      { type: 'set_two_level_grid', enabled: twoLevelGrid },
+      { type: 'spawn_random_nonoverlap_fast', count: 40, radius: 64, speed: 32 },
+      { type: 'spawn_random_nonoverlap_fast', count: 6000, radius: 4, speed: 64 },
+    ],
+    0
+  );
+  return measure(simulation, steps);
+}
+
+export function runBroadphaseCrossover(
+  bodyCounts: readonly number[] = [8, 16, 32, 64, 128, 256],
+  seed = 1,
+  steps = 600
+): BroadphaseComparison[] {
+  const run = (broadphase: Broadphase, bodyCount: number): BenchmarkResult => {
+    const simulation = new GameSimulation({ seed });
+    simulation.next_tick(
+      [
+        { type: 'set_broadphase', mode: broadphase },
+        { type: 'spawn_random_nonoverlap_fast', count: bodyCount, radius: 8, speed: 96 },
+      ],
+      0
+    );
+    return measure(simulation, steps);
+  };
+
+  return bodyCounts.map((bodyCount) => ({
+    bodies: bodyCount,
+    gridMsPerStep: run('grid', bodyCount).msPerStep,
+    bruteForceMsPerStep: run('brute_force', bodyCount).msPerStep,
+  }));
+}
+
+export function runGridModeBenchmark(
+  seed = 1,
+  bodyCount = 5000,
+  steps = 600
+): GridModeComparison {
+  const run = (mode: GridMode): BenchmarkResult => {
+    const simulation = new GameSimulation({ seed });
+    simulation.next_tick(
+      [
+        { type: 'set_grid_mode', mode },
+        { type: 'spawn_random_nonoverlap_fast', count: bodyCount, radius: 4, speed: 6 },
+      ],
+      0
+    );
+    return measure(simulation, steps);
+  };
+
+  const rebuild = run('rebuild');
+  return {
+    bodies: rebuild.bodies,
+    rebuildMsPerStep: rebuild.msPerStep,
+    incrementalMsPerStep: run('incremental').msPerStep,
+  };
+}
+
+function measure(simulation: GameSimulation, steps: number): BenchmarkResult {
+  const bodies = simulation.get_state().entities.length;
+
+  const deltaTime = 1 / 30;
+  const start = performance.now();
+  for (let i = 0; i < steps; i++) {
+    simulation.next_tick([], deltaTime);
+  }
+  const totalMs = performance.now() - start;
+
+  return { bodies, steps, totalMs, msPerStep: totalMs / steps };
+}
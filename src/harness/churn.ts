@@ -0,0 +1,78 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { createRng } from '../GameSimulation/rng.js';
+
+export interface ChurnResult {
+  frames: number;
+  spawned: number;
+  removed: number;
+  finalCount: number;
+}
+
+export function runChurn(frames: number, seed = 1): ChurnResult {
+  const simulation = new GameSimulation({ seed });
+  const rng = createRng('lcg', seed ^ 0x9e3779b9);
+  const deltaTime = 1 / 60;
+  let spawned = 0;
+  let removed = 0;
+
+  for (let frame = 0; frame < frames; frame++) {
+    const before = simulation.get_state().entities.length;
+    const count = Math.floor(rng.nextFloat() * 64);
+    const radius = rng.nextRange(2, 10);
+    simulation.next_tick([{ type: 'spawn_random_entities', count, radius, speed: 120 }], 0);
+    spawned += simulation.get_state().entities.length - before;
+
+    const entities = simulation.get_state().entities;
+    const ids: number[] = [];
+    for (const entity of entities) {
+      if (rng.nextFloat() < 0.1) {
+        ids.push(entity.id);
+      }
+    }
+    removed += simulation.remove_entities(ids);
+
+    simulation.next_tick([], deltaTime);
+    checkInvariants(simulation);
+  }
+
+  return { frames, spawned, removed, finalCount: simulation.get_state().entities.length };
+}
+
+function checkInvariants(simulation: GameSimulation): void {
+  const fail = (message: string, ids: number[]): never => {
+    const context = simulation.get_debug_context(ids);
+    throw new Error(
+      `Churn: ${message} [frame ${context.frame}, seed ${context.seed}, ` +
+        `entities ${context.entityIds.join(', ')}]`
+    );
+  };
+
+  const state = simulation.get_state();
+  const cellSize = state.gridCellSize;
+  const cols = Math.ceil(state.width / cellSize);
+  const rows = Math.ceil(state.height / cellSize);
+  const ids = new Set<number>();
+
+  for (const entity of state.entities) {
+    if (ids.has(entity.id)) {
+      fail('duplicate entity id.', [entity.id]);
+    }
+    ids.add(entity.id);
+
+    const body = entity.body;
+    if (!body) continue;
+
+    if (!Number.isFinite(body.x) || !Number.isFinite(body.y)) {
+      fail('non-finite position.', [entity.id]);
+    }
+
+    const cell = simulation.point_to_cell(body.x, body.y);
+    if (cell.col < 0 || cell.col >= cols || cell.row < 0 || cell.row >= rows) {
+      fail(`cell (${cell.col}, ${cell.row}) is outside the ${cols}x${rows} grid.`, [entity.id]);
+    }
+
+    if (simulation.inspect_entity(entity.id)?.id !== entity.id) {
+      fail('id index lost the entity.', [entity.id]);
+    }
+  }
+}
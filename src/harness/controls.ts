@@ -1,6 +1,12 @@
 import { SimulationRunner } from './simulation-runner.js';
 import { AppState } from './app-state.js';
 import { SimulationCommand } from '../GameSimulation/GameSimulation.js';
+import {
+  runBenchmark,
+  runBroadphaseCrossover,
+  runGridModeBenchmark,
+  runMixedSizeBenchmark,
+} from './benchmark.js';
 
 export function setupControls(runner: SimulationRunner, appState: AppState): void {
   const gridInfo = document.getElementById('grid-info');
@@ -34,7 +40,7 @@ export function setupControls(runner: SimulationRunner, appState: AppState): voi
   const minusBtn = document.getElementById('minus-btn');
   if (minusBtn) {
     minusBtn.addEventListener('click', () => {
-      queueCommands([{ type: 'remove_half_entities' }]);
+      queueCommands([{ type: 'remove_random_fraction', fraction: 0.5 }]);
     });
   }
 
@@ -69,6 +75,16 @@ export function setupControls(runner: SimulationRunner, appState: AppState): voi
     });
   }
 
+  const gridModeBtn = document.getElementById('grid-mode-btn');
+  if (gridModeBtn) {
+    let incremental = false;
+    gridModeBtn.addEventListener('click', () => {
+      incremental = !incremental;
+      queueCommands([{ type: 'set_grid_mode', mode: incremental ? 'incremental' : 'rebuild' }]);
+      gridModeBtn.textContent = `Grid: ${incremental ? 'Incremental' : 'Rebuild'}`;
+    });
+  }
+
   const renderBtn = document.getElementById('render-btn');
   if (renderBtn) {
     renderBtn.addEventListener('click', () => {
@@ -109,5 +125,29 @@ export function setupControls(runner: SimulationRunner, appState: AppState): voi
     });
   }
 
+  const benchBtn = document.getElementById('bench-btn');
+  const benchInfo = document.getElementById('bench-info');
+  if (benchBtn) {
+    benchBtn.addEventListener('click', () => {
+      if (benchInfo) benchInfo.textContent = 'Benchmark: running...';
+
+      setTimeout(() => {
+        const result = runBenchmark();
+        const singleGrid = runMixedSizeBenchmark(false);
+        const twoLevelGrid = runMixedSizeBenchmark(true);
+        const summary =
+          `Benchmark: ${result.bodies} bodies, ${result.steps} steps, ` +
+          `${result.msPerStep.toFixed(2)} ms/step | Mixed sizes: ` +
+          `${singleGrid.msPerStep.toFixed(2)} ms/step (one grid), ` +
+          `${twoLevelGrid.msPerStep.toFixed(2)} ms/step (two-level)`;
+        if (benchInfo) benchInfo.textContent = summary;
+        console.log(summary);
+        console.table(runBroadphaseCrossover());
+        console.table([runGridModeBenchmark()]);
+        appState.lastTime = performance.now();
+      }, 0);
+    });
+  }
+
   updateGridInfo();
 }
@@ -117,7 +117,7 @@ export class Renderer {
 
       sprite.position.set(b.x, b.y);
       const scale = b.radius / this.baseRadius;
-      sprite.scale.set(scale, scale);
+      sprite.scale.set(scale, scale * b.aspect);
       used++;
     }
 
@@ -0,0 +1,14 @@
+import { runChurn } from '../harness/churn.js';
+import { assert, test } from './runner.js';
+
+test('seeded spawn/remove churn keeps ids, positions and grid cells valid', () => {
+  for (const seed of [1, 2, 3]) {
+    const result = runChurn(300, seed);
+    assert(result.spawned > 0 && result.removed > 0, `seed ${seed} did not churn`);
+    assert(
+      result.finalCount === result.spawned - result.removed,
+      `seed ${seed} ended with ${result.finalCount} bodies after ${result.spawned} spawned ` +
+        `and ${result.removed} removed`
+    );
+  }
+});
@@ -0,0 +1,229 @@
+import { CollisionModel, GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, assertClose, test } from './runner.js';
+
+function headOn(model: CollisionModel): { a: number; b: number; overlapping: boolean } {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 200,
+      bodies: [
+        { x: 100, y: 100, vx: 60, radius: 10 },
+        { x: 121, y: 100, vx: -60, radius: 10 },
+      ],
+    })
+  );
+  const [first, second] = simulation.get_snapshot();
+  simulation.next_tick([{ type: 'set_collision_model', model }], 1 / 60);
+
+  return {
+    a: simulation.inspect_entity(first.id)?.vx ?? NaN,
+    b: simulation.inspect_entity(second.id)?.vx ?? NaN,
+    overlapping: simulation.are_colliding(first.id, second.id),
+  };
+}
+
+test('impulse_elastic swaps the normal velocities of equal bodies', () => {
+  const result = headOn('impulse_elastic');
+  assertClose(result.a, -60, 1e-9, 'left body velocity');
+  assertClose(result.b, 60, 1e-9, 'right body velocity');
+  assert(!result.overlapping, 'bodies still overlap after the solver');
+});
+
+test('positional_only separates bodies without touching their velocities', () => {
+  const result = headOn('positional_only');
+  assertClose(result.a, 60, 1e-9, 'left body velocity');
+  assertClose(result.b, -60, 1e-9, 'right body velocity');
+  assert(!result.overlapping, 'bodies still overlap after the solver');
+});
+
+test('inelastic leaves both bodies with their shared normal velocity', () => {
+  const result = headOn('inelastic');
+  assertClose(result.a, 0, 1e-9, 'left body velocity');
+  assertClose(result.b, 0, 1e-9, 'right body velocity');
+  assert(!result.overlapping, 'bodies still overlap after the solver');
+});
+
+test('a resting row inside the correction slop does not creep apart', () => {
+  const bodies = [100, 119.5, 139, 158.5, 178].map((x) => ({ x, y: 390, radius: 10 }));
+  const simulation = GameSimulation.from_scene(JSON.stringify({ width: 400, height: 400, bodies }));
+  simulation.next_tick(
+    [
+      { type: 'set_gravity', x: 0, y: 100 },
+      { type: 'set_wall_restitution', restitution: 0 },
+      { type: 'set_position_correction', slop: 1, percent: 0.8 },
+    ],
+    0
+  );
+  for (let frame = 0; frame < 300; frame++) {
+    simulation.next_tick([], 1 / 60);
+  }
+
+  const snapshot = simulation.get_snapshot();
+  for (let i = 0; i < bodies.length; i++) {
+    assertClose(snapshot[i].x, bodies[i].x, 1e-9, `body ${i} x`);
+    assertClose(snapshot[i].y, 390, 1e-9, `body ${i} y`);
+  }
+  assert(simulation.count_overlaps() === 4, 'the resting contacts were pushed apart');
+});
+
+function clump(limit: number): GameSimulation {
+  const bodies = [];
+  for (let i = 0; i < 16; i++) {
+    bodies.push({ x: 200 + (i % 8) * 1.5, y: 200 + Math.floor(i / 8) * 1.5, radius: 5 });
+  }
+  const simulation = GameSimulation.from_scene(JSON.stringify({ width: 400, height: 400, bodies }));
+  simulation.next_tick(
+    [
+      { type: 'set_collision_model', model: 'positional_only' },
+      { type: 'set_max_neighbors_per_body', limit },
+    ],
+    0
+  );
+  return simulation;
+}
+
+test('a neighbour cap bounds per-body pair checks and still separates a dense clump', () => {
+  const uncapped = clump(0);
+  uncapped.next_tick([], 1 / 60);
+  const uncappedChecks = uncapped.get_pair_check_count();
+  assert(uncappedChecks > 16 * 2, `the uncapped clump only checked ${uncappedChecks} pairs`);
+
+  const simulation = clump(2);
+  for (let frame = 0; frame < 300; frame++) {
+    simulation.next_tick([], 1 / 60);
+    const checks = simulation.get_pair_check_count();
+    assert(checks <= 16 * 2, `frame ${frame} checked ${checks} pairs under a cap of 2`);
+  }
+
+  const snapshot = simulation.get_snapshot();
+  let deepest = 0;
+  for (let i = 0; i < snapshot.length; i++) {
+    for (let j = i + 1; j < snapshot.length; j++) {
+      const a = snapshot[i];
+      const b = snapshot[j];
+      const distance = Math.sqrt((a.x - b.x) ** 2 + (a.y - b.y) ** 2);
+      deepest = Math.max(deepest, a.radius + b.radius - distance);
+    }
+  }
+  assert(deepest < 1e-6, `the capped solver left a ${deepest} overlap in the clump`);
+});
+
+function cradle(fromLeft: boolean, slop: number): number[] {
+  const bodies = [200, 220, 240, 260, 280].map((x) => ({ x, y: 100, radius: 10 }));
+  bodies.push(
+    fromLeft ? { x: 179.5, y: 100, vx: 60, radius: 10 } : { x: 300.5, y: 100, vx: -60, radius: 10 }
+  );
+  const simulation = GameSimulation.from_scene(JSON.stringify({ width: 500, height: 200, bodies }));
+  const phases: string[] = [];
+  simulation.next_tick(
+    [
+      { type: 'set_contact_passes', passes: 3 },
+      { type: 'set_position_correction', slop, percent: 1 },
+    ],
+    1 / 60,
+    (phase) => phases.push(phase)
+  );
+  assert(
+    phases.join(',') === 'integrated,solver_pass,solver_pass,solver_pass,completed',
+    `observer saw ${phases.join(',')}`
+  );
+
+  return simulation.get_snapshot().map((record) => record.vx);
+}
+
+test('contact passes carry a striker through a five-body cradle in either direction', () => {
+  for (const slop of [0, 1]) {
+    for (const fromLeft of [true, false]) {
+      const velocities = cradle(fromLeft, slop);
+      const far = fromLeft ? 4 : 0;
+      for (let i = 0; i < velocities.length; i++) {
+        const expected = i === far ? (fromLeft ? 60 : -60) : 0;
+        const side = fromLeft ? 'left' : 'right';
+        assertClose(velocities[i], expected, 1e-9, `body ${i} from the ${side} with slop ${slop}`);
+      }
+    }
+  }
+});
+
+test('correction damping keeps a dense pile at rest under gravity from gaining energy', () => {
+  const bodies = [];
+  for (let i = 0; i < 64; i++) {
+    bodies.push({ x: 5 + (i % 16) * 6, y: 195 - Math.floor(i / 16) * 6, radius: 5 });
+  }
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({ width: 96, height: 200, bodies }),
+    { seed: 5 }
+  );
+  const gravity = 200;
+  const deltaTime = 1 / 60;
+  simulation.next_tick(
+    [
+      { type: 'set_gravity', x: 0, y: gravity },
+      { type: 'set_debug_checks', enabled: true },
+      { type: 'set_correction_damping', damping: 4 },
+    ],
+    0
+  );
+
+  const fallSpeed = 3 * gravity * deltaTime;
+  const bound = bodies.length * 0.5 * fallSpeed * fallSpeed;
+  for (let frame = 0; frame < 600; frame++) {
+    simulation.next_tick([], deltaTime);
+    let energy = 0;
+    for (const record of simulation.get_snapshot()) {
+      energy += 0.5 * (record.vx * record.vx + record.vy * record.vy);
+    }
+    assert(energy < bound, `pile kinetic energy reached ${energy} on frame ${frame}`);
+  }
+});
+
+function spinAfterHit(offsetY: number): number[] {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 200,
+      bodies: [
+        { x: 100, y: 100, vx: 60, radius: 10 },
+        { x: 119, y: 100 + offsetY, radius: 10 },
+      ],
+    })
+  );
+  simulation.next_tick([{ type: 'set_rotational_collisions', enabled: true }], 1 / 60);
+  return simulation.get_state().entities.map((entity) => entity.body?.angularVelocity ?? NaN);
+}
+
+test('rotational collisions spin bodies on an off-centre hit but not a head-on one', () => {
+  const [strikerSpin, targetSpin] = spinAfterHit(8);
+  assert(Math.abs(strikerSpin) > 0.1, `striker spin was ${strikerSpin}`);
+  assert(Math.abs(targetSpin) > 0.1, `target spin was ${targetSpin}`);
+
+  for (const spin of spinAfterHit(0)) {
+    assertClose(spin, 0, 1e-12, 'head-on spin');
+  }
+});
+
+test('wall restitution 1 with body restitution 0 bounces off walls but sticks on contact', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 200,
+      bodies: [
+        { x: 100, y: 100, vx: 60, radius: 10 },
+        { x: 121, y: 100, vx: -60, radius: 10 },
+        { x: 389.5, y: 50, vx: 60, radius: 10 },
+      ],
+    })
+  );
+  const [left, right, wallBody] = simulation.get_snapshot();
+  simulation.next_tick(
+    [
+      { type: 'set_wall_restitution', restitution: 1 },
+      { type: 'set_body_restitution', restitution: 0 },
+    ],
+    1 / 60
+  );
+
+  assertClose(simulation.inspect_entity(left.id)?.vx ?? NaN, 0, 1e-9, 'left body velocity');
+  assertClose(simulation.inspect_entity(right.id)?.vx ?? NaN, 0, 1e-9, 'right body velocity');
+  assertClose(simulation.inspect_entity(wallBody.id)?.vx ?? NaN, -60, 1e-9, 'wall bounce velocity');
+});
@@ -0,0 +1,35 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, assertClose, test } from './runner.js';
+
+test('a spring hanging from a pinned anchor settles under gravity', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 400,
+      bodies: [
+        { x: 200, y: 50, radius: 5 },
+        { x: 200, y: 150, radius: 5 },
+      ],
+    })
+  );
+  const [anchor, bob] = simulation.get_snapshot();
+  simulation.add_spring(anchor.id, bob.id, 100, 20, 4);
+  simulation.next_tick(
+    [
+      { type: 'pin_entity', id: anchor.id, pinned: true },
+      { type: 'set_gravity', x: 0, y: 100 },
+    ],
+    1 / 60
+  );
+  for (let frame = 0; frame < 600; frame++) {
+    simulation.next_tick([], 1 / 60);
+  }
+
+  const anchorAfter = simulation.inspect_entity(anchor.id);
+  const bobAfter = simulation.inspect_entity(bob.id);
+  assert(anchorAfter !== null && bobAfter !== null, 'a spring body was removed');
+  assert(anchorAfter.x === 200 && anchorAfter.y === 50, 'the pinned anchor moved');
+  assertClose(bobAfter.x, 200, 1e-6, 'bob x');
+  assertClose(bobAfter.y, 155, 0.05, 'bob y at rest length plus gravity / stiffness');
+  assertClose(bobAfter.vy, 0, 0.01, 'bob vertical speed');
+});
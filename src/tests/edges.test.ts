@@ -0,0 +1,106 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, assertClose, test } from './runner.js';
+
+function singleBody(vx: number, vy: number): { simulation: GameSimulation; id: number } {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({ width: 400, height: 400, bodies: [{ x: 200, y: 200, vx, vy, radius: 5 }] })
+  );
+  return { simulation, id: simulation.get_snapshot()[0].id };
+}
+
+test('a body settles on the floor of the inset bounds', () => {
+  const { simulation, id } = singleBody(80, 0);
+  simulation.next_tick(
+    [
+      { type: 'set_bounds_inset', inset: 20 },
+      { type: 'set_wall_restitution', restitution: 0.5 },
+      { type: 'set_gravity', x: 0, y: 200 },
+    ],
+    0
+  );
+
+  for (let frame = 0; frame < 600; frame++) {
+    simulation.next_tick([], 1 / 60);
+    const body = simulation.inspect_entity(id);
+    assert(body !== null, 'the body left the world');
+    assert(body.x >= 25 && body.x <= 375, `x ${body.x} left the inset bounds`);
+    assert(body.y >= 25 && body.y <= 375, `y ${body.y} left the inset bounds`);
+  }
+
+  assertClose(simulation.inspect_entity(id)?.y ?? NaN, 375, 0.1, 'resting height');
+});
+
+test('wrap on x carries a body across while the floor still bounces it', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 400,
+      bodies: [{ x: 399, y: 394.5, vx: 120, vy: 60, radius: 5 }],
+    })
+  );
+  const id = simulation.get_snapshot()[0].id;
+  simulation.next_tick([{ type: 'set_wrap_x', enabled: true }], 1 / 60);
+
+  const body = simulation.inspect_entity(id);
+  assert(body !== null, 'the wrapped body was removed');
+  assertClose(body.x, 1, 1e-9, 'x after wrapping');
+  assertClose(body.vx, 120, 1e-9, 'vx after wrapping');
+  assertClose(body.y, 395, 1e-9, 'y after the floor bounce');
+  assertClose(body.vy, -60, 1e-9, 'vy after the floor bounce');
+});
+
+test('an absorbing floor leaves falling bodies at rest flush against it', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 400,
+      bodies: [
+        { x: 100, y: 50, vx: 20, radius: 5 },
+        { x: 200, y: 200, vy: 40, radius: 8 },
+        { x: 300, y: 350, vx: -30, vy: -50, radius: 12 },
+      ],
+    })
+  );
+  simulation.next_tick(
+    [
+      { type: 'set_edge_behavior', edge: 'bottom', behavior: 'absorb' },
+      { type: 'set_gravity', x: 0, y: 300 },
+    ],
+    0
+  );
+  for (let frame = 0; frame < 180; frame++) {
+    simulation.next_tick([], 1 / 60);
+  }
+
+  for (const record of simulation.get_snapshot()) {
+    assertClose(record.y, 400 - record.radius, 1e-9, `body ${record.id} height`);
+    assert(record.vx === 0 && record.vy === 0, `body ${record.id} is still moving`);
+  }
+});
+
+test('the contact solver cannot push a body through a solid wall', () => {
+  const bodies = [];
+  for (let i = 0; i < 48; i++) {
+    bodies.push({ x: 380 + (i % 4) * 4, y: 380 + Math.floor(i / 4) * 1.5, radius: 5 });
+  }
+  const simulation = GameSimulation.from_scene(JSON.stringify({ width: 400, height: 400, bodies }));
+  simulation.next_tick(
+    [
+      { type: 'set_gravity', x: 400, y: 400 },
+      { type: 'set_position_correction', slop: 0, percent: 1 },
+    ],
+    0
+  );
+
+  for (let frame = 0; frame < 120; frame++) {
+    simulation.next_tick([], 1 / 60);
+    for (const entity of simulation.get_state().entities) {
+      const body = entity.body;
+      if (!body) continue;
+      assert(
+        body.x + body.radius <= 400 && body.y + body.radius <= 400,
+        `body ${entity.id} at (${body.x}, ${body.y}) crossed the wall on frame ${frame}`
+      );
+    }
+  }
+});
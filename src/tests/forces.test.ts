@@ -0,0 +1,85 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, assertClose, test } from './runner.js';
+
+function restingBody(): { simulation: GameSimulation; id: number } {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({ width: 400, height: 400, bodies: [{ x: 200, y: 200, radius: 5 }] })
+  );
+  return { simulation, id: simulation.get_snapshot()[0].id };
+}
+
+test('an applied force changes velocity by force times delta time for one frame', () => {
+  const { simulation, id } = restingBody();
+  simulation.next_tick([{ type: 'apply_force', id, fx: 120, fy: -60 }], 1 / 60);
+
+  const afterForce = simulation.inspect_entity(id);
+  assertClose(afterForce?.vx ?? NaN, 2, 1e-9, 'vx after the forced frame');
+  assertClose(afterForce?.vy ?? NaN, -1, 1e-9, 'vy after the forced frame');
+
+  simulation.next_tick([], 1 / 60);
+  const nextFrame = simulation.inspect_entity(id);
+  assertClose(nextFrame?.vx ?? NaN, 2, 1e-9, 'vx once the accumulator is cleared');
+  assertClose(nextFrame?.vy ?? NaN, -1, 1e-9, 'vy once the accumulator is cleared');
+});
+
+test('per-body drag compounds with global damping and separates body speeds', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 4000,
+      height: 400,
+      bodies: [
+        { x: 100, y: 100, vx: 100, radius: 5 },
+        { x: 100, y: 300, vx: 100, radius: 5 },
+      ],
+    })
+  );
+  const [light, heavy] = simulation.get_snapshot();
+  simulation.next_tick(
+    [
+      { type: 'set_damping', damping: 0.1 },
+      { type: 'set_entity_drag', id: light.id, drag: 0.5 },
+      { type: 'set_entity_drag', id: heavy.id, drag: 2 },
+    ],
+    0
+  );
+  for (let frame = 0; frame < 60; frame++) {
+    simulation.next_tick([], 1 / 60);
+  }
+
+  const damping = 1 - 0.1 / 60;
+  const lightVx = simulation.inspect_entity(light.id)?.vx ?? NaN;
+  const heavyVx = simulation.inspect_entity(heavy.id)?.vx ?? NaN;
+  assertClose(lightVx, 100 * ((1 - 0.5 / 60) * damping) ** 60, 1e-9, 'low-drag speed');
+  assertClose(heavyVx, 100 * ((1 - 2 / 60) * damping) ** 60, 1e-9, 'high-drag speed');
+  assert(lightVx - heavyVx > 20, 'bodies with different drag kept similar speeds');
+});
+
+test('a body with gravity scale 0 floats while its neighbour falls', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 400,
+      bodies: [
+        { x: 150, y: 100, radius: 5 },
+        { x: 250, y: 100, radius: 5 },
+      ],
+    })
+  );
+  const [balloon, stone] = simulation.get_snapshot();
+  simulation.next_tick(
+    [
+      { type: 'set_gravity', x: 0, y: 100 },
+      { type: 'set_entity_gravity_scale', id: balloon.id, scale: 0 },
+    ],
+    0
+  );
+  for (let frame = 0; frame < 30; frame++) {
+    simulation.next_tick([], 1 / 60);
+  }
+
+  const balloonAfter = simulation.inspect_entity(balloon.id);
+  const stoneAfter = simulation.inspect_entity(stone.id);
+  assert(balloonAfter?.y === 100 && balloonAfter.vy === 0, 'the zero-scale body moved');
+  assertClose(stoneAfter?.vy ?? NaN, 50, 1e-9, 'falling neighbour speed');
+  assert((stoneAfter?.y ?? 0) > 110, 'the neighbour did not fall');
+});
@@ -0,0 +1,24 @@
+import './churn.test.js';
+import './collision.test.js';
+import './constraints.test.js';
+import './edges.test.js';
+import './forces.test.js';
+import './snapshot.test.js';
+import './spatial-grid.test.js';
+import './spawn.test.js';
+import './world.test.js';
+import { runTests } from './runner.js';
+
+const results = runTests();
+const failures = results.filter((result) => result.error !== null);
+for (const result of results) {
+  console.log(`${result.error === null ? 'ok  ' : 'FAIL'} ${result.name}`);
+  if (result.error !== null) {
+    console.log(`     ${result.error}`);
+  }
+}
+console.log(`${results.length - failures.length}/${results.length} tests passed`);
+
+if (failures.length > 0) {
+  throw new Error(`${failures.length} test(s) failed.`);
+}
@@ -0,0 +1,38 @@
+export interface TestResult {
+  name: string;
+  error: string | null;
+}
+
+interface TestCase {
+  name: string;
+  run: () => void;
+}
+
+const cases: TestCase[] = [];
+
+export function test(name: string, run: () => void): void {
+  cases.push({ name, run });
+}
+
+export function assert(condition: boolean, message: string): asserts condition {
+  if (!condition) {
+    throw new Error(message);
+  }
+}
+
+export function assertClose(actual: number, expected: number, tolerance: number, message: string): void {
+  if (!(Math.abs(actual - expected) <= tolerance)) {
+    throw new Error(`${message}: expected ${expected} ± ${tolerance}, got ${actual}.`);
+  }
+}
+
+export function runTests(): TestResult[] {
+  return cases.map((testCase) => {
+    try {
+      testCase.run();
+      return { name: testCase.name, error: null };
+    } catch (error) {
+      return { name: testCase.name, error: error instanceof Error ? error.message : String(error) };
+    }
+  });
+}
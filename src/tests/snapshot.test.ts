@@ -0,0 +1,40 @@
+import {
+  applyDelta,
+  diffSnapshots,
+  EntityRecord,
+  GameSimulation,
+} from '../GameSimulation/GameSimulation.js';
+import { assert, test } from './runner.js';
+
+function byId(records: readonly EntityRecord[]): string {
+  return JSON.stringify([...records].sort((a, b) => a.id - b.id));
+}
+
+test('snapshot deltas round-trip a sequence of frames with spawns and removals', () => {
+  const simulation = new GameSimulation({ seed: 11 });
+  let previous: EntityRecord[] = [];
+  let replayed: EntityRecord[] = [];
+
+  for (let frame = 0; frame < 120; frame++) {
+    if (frame % 10 === 0) {
+      simulation.next_tick([{ type: 'spawn_random_entities', count: 20, radius: 6, speed: 90 }], 0);
+    }
+    if (frame % 15 === 7) {
+      const ids = simulation.get_snapshot().map((record) => record.id);
+      simulation.remove_entities(ids.filter((_, index) => index % 3 === 0));
+    }
+    simulation.next_tick([], 1 / 60);
+
+    const next = simulation.get_snapshot();
+    const delta = diffSnapshots(previous, next);
+    replayed = applyDelta(replayed, delta);
+    assert(byId(replayed) === byId(next), `frame ${frame} did not round-trip`);
+    previous = next;
+  }
+
+  const settled = diffSnapshots(previous, simulation.get_snapshot());
+  assert(
+    settled.added.length + settled.removed.length + settled.modified.length === 0,
+    'an unchanged frame produced a non-empty delta'
+  );
+});
@@ -0,0 +1,144 @@
+import { Body, Entity } from '../GameSimulation/components.js';
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { SpatialGrid } from '../GameSimulation/spatial-grid.js';
+import { assert, assertClose, test } from './runner.js';
+
+test('a large body finds a small neighbour several cells from its centre', () => {
+  const grid = new SpatialGrid(1000, 1000, 24);
+  const large = new Entity(1, new Body(500, 500, 0, 0, 200));
+  const small = new Entity(2, new Body(701, 500, 0, 0, 4));
+  grid.insert(large);
+  grid.insert(small);
+
+  assert(grid.query(large).includes(small), 'query from the large body missed the small body');
+  assert(grid.query(small).includes(large), 'query from the small body missed the large body');
+
+  const inRadius = grid.getEntitiesInRadius(500, 500, 204);
+  assert(inRadius.length === 2, `expected both bodies within the summed radii, got ${inRadius.length}`);
+});
+
+test('a stationary radius-200 body collides with a radius-4 body entering its edge', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 1000,
+      height: 1000,
+      cellSize: 24,
+      bodies: [
+        { x: 500, y: 500, radius: 200 },
+        { x: 706, y: 500, vx: -120, radius: 4 },
+      ],
+    })
+  );
+  const [large, small] = simulation.get_snapshot();
+
+  simulation.next_tick([], 1 / 30);
+
+  const pairs = simulation.get_contact_pairs();
+  let found = false;
+  for (let i = 0; i < pairs.length; i += 2) {
+    const ids = [pairs[i], pairs[i + 1]];
+    if (ids.includes(large.id) && ids.includes(small.id)) {
+      found = true;
+    }
+  }
+  assert(found, 'the solver never saw the large/small contact');
+
+  const smallAfter = simulation.inspect_entity(small.id);
+  assert(smallAfter !== null && smallAfter.vx > -120, 'the small body kept its approach speed');
+});
+
+test('a ray with a huge maxDistance only walks the occupied cells', () => {
+  const grid = new SpatialGrid(1000, 1000, 24);
+  const target = new Entity(1, new Body(500, 500, 0, 0, 10));
+  grid.insert(target);
+
+  const miss = grid.raycast(-1e9, 527, 1, 0, 1e12);
+  assert(miss === null, 'a ray passing below the body hit it');
+  const missVisits = grid.getLastVisitedCellCount();
+  assert(missVisits > 0 && missVisits <= 3, `the missed ray visited ${missVisits} cells`);
+
+  const hit = grid.raycast(-1e5, 500, 1, 0, 1e12);
+  assert(hit !== null && hit.entity.id === target.id, 'a ray from far away missed the body');
+  assertClose(hit.distance, 1e5 + 490, 1e-6, 'hit distance');
+  const hitVisits = grid.getLastVisitedCellCount();
+  assert(hitVisits > 0 && hitVisits <= 2, `the hitting ray visited ${hitVisits} cells`);
+});
+
+test('a wide capsule falls back to scanning the occupied cells', () => {
+  const grid = new SpatialGrid(1000, 1000, 4);
+  const near = new Entity(1, new Body(300, 300, 0, 0, 2));
+  const far = new Entity(2, new Body(900, 900, 0, 0, 2));
+  grid.insert(near);
+  grid.insert(far);
+
+  const found = grid.queryCapsule(0, 0, 1e9, 0, 400);
+  assert(found.length === 1 && found[0].id === near.id, 'the capsule did not return the near body');
+  const visits = grid.getLastVisitedCellCount();
+  assert(
+    visits === grid.getActiveCellCount(),
+    `the capsule visited ${visits} cells for ${grid.getActiveCellCount()} occupied ones`
+  );
+});
+
+test('incremental grid mode sees bodies spawned and removed since the last frame', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({ width: 400, height: 400, bodies: [{ x: 50, y: 50, radius: 5 }] })
+  );
+  simulation.next_tick([{ type: 'set_grid_mode', mode: 'incremental' }], 1 / 60);
+
+  const first = simulation.try_spawn_at(300, 300, 5, 0, 0);
+  const second = simulation.try_spawn_at(300, 300, 5, 0, 0);
+  assert(first !== 0, 'the first spawn into empty space failed');
+  assert(second === 0, 'a second spawn at the same point overlapped the first');
+
+  simulation.remove_entities([first]);
+  assert(simulation.try_spawn_at(300, 300, 5, 0, 0) !== 0, 'a removed body still blocked spawning');
+});
+
+test('every cell across the world range keeps its own contents', () => {
+  const cellSize = 24;
+  const grid = new SpatialGrid(2500, 1200, cellSize);
+  const cols = Math.ceil(2500 / cellSize);
+  const rows = Math.ceil(1200 / cellSize);
+  const edge = 1 << 25;
+  const probes: [number, number][] = [
+    [-edge, -edge],
+    [-edge, edge - 1],
+    [edge - 1, -edge],
+    [edge - 1, edge - 1],
+  ];
+  for (let col = -1; col <= cols; col++) {
+    for (let row = -1; row <= rows; row++) {
+      probes.push([col, row]);
+    }
+  }
+
+  const entities = probes.map(([col, row], index) => {
+    const x = (col + 0.5) * cellSize;
+    const y = (row + 0.5) * cellSize;
+    const entity = new Entity(index + 1, new Body(x, y, 0, 0, 1));
+    grid.insert(entity);
+    return entity;
+  });
+
+  for (let i = 0; i < probes.length; i++) {
+    const [col, row] = probes[i];
+    const contents = grid.getCellContents(col, row);
+    assert(
+      contents.length === 1 && contents[0] === entities[i],
+      `cell (${col}, ${row}) holds ${contents.length} bodies`
+    );
+  }
+});
+
+test('try_spawn_at refuses bodies that would cross the walls or the inset', () => {
+  const scene = JSON.stringify({ width: 400, height: 400, bodies: [] });
+  const simulation = GameSimulation.from_scene(scene);
+  assert(simulation.try_spawn_at(3, 200, 5, 0, 0) === 0, 'a body crossing the left wall spawned');
+  assert(simulation.try_spawn_at(200, 450, 5, 0, 0) === 0, 'a body below the floor spawned');
+  assert(simulation.try_spawn_at(200, 200, 5, 0, 0) !== 0, 'a body inside the walls was refused');
+
+  simulation.next_tick([{ type: 'set_bounds_inset', inset: 20 }], 0);
+  assert(simulation.try_spawn_at(390, 100, 5, 0, 0) === 0, 'a body in the inset margin spawned');
+  assert(simulation.try_spawn_at(375, 100, 5, 0, 0) !== 0, 'a body touching the inset was refused');
+});
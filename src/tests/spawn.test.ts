@@ -0,0 +1,146 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, assertClose, test } from './runner.js';
+
+test('a crowded non-overlapping spawn only evicts one body per placed body', () => {
+  const simulation = new GameSimulation({ seed: 7 });
+  simulation.next_tick(
+    [
+      { type: 'set_entity_budget', budget: 10 },
+      { type: 'spawn_random_static', count: 10, radius: 2 },
+      { type: 'spawn_random_nonoverlap_fast', count: 10, radius: 300, speed: 0 },
+    ],
+    0
+  );
+
+  const placed = simulation.get_snapshot().filter((record) => record.radius === 300).length;
+  assert(placed > 0 && placed < 10, `expected a partial batch, placed ${placed}`);
+  assert(
+    simulation.get_evicted_count() === placed,
+    `evicted ${simulation.get_evicted_count()} bodies for ${placed} placed`
+  );
+  assert(simulation.get_snapshot().length === 10, 'the world left its budget');
+});
+
+test('zero_net_momentum spawns cancel the batch momentum', () => {
+  const simulation = new GameSimulation({ seed: 9 });
+  simulation.next_tick(
+    [{ type: 'spawn_random_entities', count: 50, radius: 4, speed: 80, zeroNetMomentum: true }],
+    0
+  );
+  const momentum = simulation.get_momentum();
+  assertClose(momentum.x, 0, 1e-9, 'momentum x');
+  assertClose(momentum.y, 0, 1e-9, 'momentum y');
+
+  const drifting = new GameSimulation({ seed: 9 });
+  drifting.next_tick([{ type: 'spawn_random_entities', count: 50, radius: 4, speed: 80 }], 0);
+  const drift = drifting.get_momentum();
+  assert(Math.abs(drift.x) + Math.abs(drift.y) > 1, 'the plain batch already had no momentum');
+});
+
+test('placement_ignores_existing lays out a batch the same way across spawn histories', () => {
+  const scene = (bodies: object[]): GameSimulation =>
+    GameSimulation.from_scene(JSON.stringify({ width: 800, height: 600, bodies }), { seed: 42 });
+  const crowded = scene([
+    { x: 100, y: 100, radius: 30 },
+    { x: 400, y: 300, radius: 60 },
+    { x: 700, y: 500, radius: 20 },
+  ]);
+  crowded.next_tick(
+    [
+      { type: 'randomize_velocities', maxSpeed: 40 },
+      { type: 'spawn_random_entities', count: 15, radius: 6, speed: 30 },
+      { type: 'spawn_random_static', count: 5, radius: 3 },
+      { type: 'set_placement_ignores_existing', enabled: true },
+      { type: 'spawn_random_static', count: 7, radius: 2, placementStream: 9 },
+    ],
+    1 / 60
+  );
+  const empty = scene([]);
+
+  const spawn = (simulation: GameSimulation): string[] => {
+    const before = simulation.get_snapshot().length;
+    simulation.next_tick(
+      [
+        { type: 'set_placement_ignores_existing', enabled: true },
+        { type: 'spawn_random_static', count: 20, radius: 4, placementStream: 1 },
+        { type: 'spawn_random_entities', count: 20, radius: 4, speed: 50, placementStream: 2 },
+      ],
+      0
+    );
+    return simulation
+      .get_snapshot()
+      .slice(before)
+      .map((record) => `${record.x},${record.y}`);
+  };
+  const crowdedLayout = spawn(crowded);
+  const emptyLayout = spawn(empty);
+
+  assert(crowdedLayout.length === 40, `spawned ${crowdedLayout.length} bodies`);
+  assert(
+    crowdedLayout.join(';') === emptyLayout.join(';'),
+    'the two worlds placed bodies differently'
+  );
+  assert(
+    crowdedLayout.slice(0, 20).join(';') !== crowdedLayout.slice(20).join(';'),
+    'batches on different streams landed on the same points'
+  );
+});
+
+test('bodies emitted in the same frame start at distinct positions', () => {
+  const scene = JSON.stringify({ width: 800, height: 400, bodies: [] });
+  const simulation = GameSimulation.from_scene(scene);
+  simulation.next_tick(
+    [
+      {
+        type: 'add_emitter',
+        x: 100,
+        y: 200,
+        rate: 300,
+        speed: 120,
+        spread: 0,
+        angle: 0,
+        radius: 1,
+      },
+    ],
+    1 / 60
+  );
+
+  const positions = simulation.get_snapshot().map((record) => `${record.x},${record.y}`);
+  assert(positions.length === 5, `emitted ${positions.length} bodies`);
+  assert(new Set(positions).size === positions.length, 'emitted bodies share a position');
+});
+
+test('poisson spawns stop at the world entity cap', () => {
+  const simulation = new GameSimulation({ seed: 4 });
+  simulation.next_tick(
+    [
+      { type: 'spawn_random_nonoverlap_fast', count: 9990, radius: 2, speed: 0 },
+      { type: 'spawn_poisson', count: 100, radius: 2, speed: 0 },
+    ],
+    0
+  );
+  const count = simulation.get_snapshot().length;
+  assert(count === 10000, `the world holds ${count} bodies`);
+});
+
+test('fragmentation never grows the world past its entity budget', () => {
+  const scene = JSON.stringify({ width: 300, height: 300, bodies: [] });
+  const simulation = GameSimulation.from_scene(scene, { seed: 12 });
+  simulation.next_tick(
+    [
+      { type: 'set_entity_budget', budget: 20 },
+      { type: 'spawn_random_entities', count: 20, radius: 16, speed: 200 },
+      { type: 'set_fragmentation', sizeThreshold: 4, speedThreshold: 20 },
+    ],
+    0
+  );
+
+  let fragments = 0;
+  for (let frame = 0; frame < 120; frame++) {
+    simulation.next_tick([], 1 / 60);
+    fragments += simulation.get_fragment_count();
+    const count = simulation.get_snapshot().length;
+    assert(count <= 20, `frame ${frame} holds ${count} bodies under a budget of 20`);
+  }
+  assert(fragments > 0, 'no body ever fragmented');
+});
@@ -0,0 +1,113 @@
+import { GameSimulation } from '../GameSimulation/GameSimulation.js';
+import { assert, test } from './runner.js';
+
+function pairScene(): GameSimulation {
+  return GameSimulation.from_scene(
+    JSON.stringify({
+      width: 200,
+      height: 200,
+      bodies: [
+        { x: 100, y: 100, vx: 30, radius: 10 },
+        { x: 115, y: 100, vx: -30, radius: 10 },
+      ],
+    }),
+    { seed: 1 }
+  );
+}
+
+test('reset_scene rewinds only its own world id counter', () => {
+  const first = pairScene();
+  const second = pairScene();
+  first.next_tick([{ type: 'reset_scene' }], 1 / 60);
+  second.next_tick([{ type: 'spawn_random_static', count: 1, radius: 4 }], 1 / 60);
+
+  const ids = second.get_snapshot().map((record) => record.id);
+  assert(ids.join(',') === '1,2,3', `second world ids were ${ids.join(',')}`);
+});
+
+test('reset_scene clears per-frame contact, movement and heat state', () => {
+  const simulation = pairScene();
+  simulation.next_tick([], 1 / 60);
+  assert(simulation.get_contact_pairs().length > 0, 'the overlapping pair made no contact');
+
+  simulation.next_tick([{ type: 'reset_scene' }], 0);
+  assert(simulation.get_contact_pairs().length === 0, 'contact pairs survived the reset');
+  assert(simulation.get_moved_ids().length === 0, 'moved ids survived the reset');
+  assert(simulation.get_heat_field().length === 0, 'the heat field survived the reset');
+  assert(simulation.get_max_displacement() === 0, 'the max displacement survived the reset');
+});
+
+test('reset_scene drops emitters, springs and last-frame counters', () => {
+  const simulation = pairScene();
+  const [first, second] = simulation.get_snapshot();
+  simulation.add_spring(first.id, second.id, 200, 50, 0);
+  simulation.next_tick(
+    [
+      { type: 'set_entity_budget', budget: 2 },
+      { type: 'add_emitter', x: 50, y: 50, rate: 60, speed: 0, spread: 0, angle: 0, radius: 2 },
+    ],
+    1 / 60
+  );
+  assert(simulation.get_evicted_count() > 0, 'the emitter did not evict under the budget');
+
+  simulation.next_tick([{ type: 'reset_scene' }, { type: 'set_entity_budget', budget: 0 }], 0);
+  assert(simulation.get_evicted_count() === 0, 'the evicted count survived the reset');
+
+  simulation.next_tick(
+    [
+      { type: 'set_fast_spawn', enabled: true },
+      { type: 'spawn_random_static', count: 2, radius: 2 },
+    ],
+    0
+  );
+  const before = simulation.get_snapshot();
+  simulation.next_tick([], 1 / 60);
+  const after = simulation.get_snapshot();
+  assert(after.length === 2, `the emitter kept spawning after the reset (${after.length} bodies)`);
+  assert(
+    after.every((record, index) => record.x === before[index].x && record.y === before[index].y),
+    'a spring from before the reset moved the new bodies'
+  );
+});
+
+test('set_next_entity_id rejects ids that are already live', () => {
+  const simulation = pairScene();
+  let rejected = false;
+  try {
+    simulation.next_tick([{ type: 'set_next_entity_id', id: 2 }], 0);
+  } catch {
+    rejected = true;
+  }
+  assert(rejected, 'an id at the highest live id was accepted');
+
+  simulation.next_tick(
+    [
+      { type: 'set_next_entity_id', id: 10 },
+      { type: 'spawn_random_static', count: 1, radius: 4 },
+    ],
+    0
+  );
+  const ids = simulation.get_snapshot().map((record) => record.id);
+  assert(ids.join(',') === '1,2,10', `ids after raising the counter were ${ids.join(',')}`);
+});
+
+test('moved ids list only bodies that moved as the population grows', () => {
+  const simulation = GameSimulation.from_scene(
+    JSON.stringify({
+      width: 400,
+      height: 400,
+      bodies: [
+        { x: 100, y: 100, radius: 5 },
+        { x: 300, y: 300, vx: 60, radius: 5 },
+      ],
+    })
+  );
+  const [resting, moving] = simulation.get_snapshot();
+  simulation.next_tick([], 1 / 60);
+  assert(simulation.get_moved_ids().join(',') === `${moving.id}`, 'wrong bodies reported moving');
+
+  simulation.next_tick([{ type: 'spawn_random_static', count: 40, radius: 2 }], 1 / 60);
+  const moved = simulation.get_moved_ids();
+  assert(moved.includes(moving.id), 'the moving body was missed after the population grew');
+  assert(!moved.includes(resting.id), 'the resting body was reported moving');
+});